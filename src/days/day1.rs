@@ -1,3 +1,5 @@
+use anyhow::Context;
+
 use crate::util::prelude::*;
 
 fn parse_line(line: &str) -> Option<u32> {
@@ -34,33 +36,32 @@ fn parse_line_with_words(line: &str) -> Option<u32> {
     Some(first * 10 + last)
 }
 
-pub fn level1(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| parse_line(line).expect("Line contains no digits"))
-        .sum()
-}
+pub struct Day;
 
-pub fn level2(input: &str) -> u32 {
-    input
-        .lines()
-        .map(|line| parse_line_with_words(line).expect("Line contains no digits"))
-        .sum()
-}
+impl Solution for Day {
+    type Parsed<'a> = Vec<&'a str>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        Ok(input.lines().collect())
+    }
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day1.txt");
-        assert_eq!(level1(test_input), 142)
+    fn part1(lines: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| parse_line(line).with_context(|| format!("no digits on line {}", i + 1)))
+            .sum()
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day1_large.txt");
-        assert_eq!(level2(test_input), 281)
+    fn part2(lines: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| {
+                parse_line_with_words(line).with_context(|| format!("no digits on line {}", i + 1))
+            })
+            .sum()
     }
 }
@@ -14,6 +14,8 @@ use nom_supreme::{
     ParserExt,
 };
 
+use anyhow::Context;
+
 use crate::util::prelude::*;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -52,7 +54,7 @@ impl Piece {
     }
 }
 
-fn parse_piece(input: &str) -> ParseResult<Piece> {
+fn parse_piece(input: &str) -> ParseResult<'_, Piece> {
     use Direction::*;
     use Piece::*;
 
@@ -71,7 +73,7 @@ fn parse_piece(input: &str) -> ParseResult<Piece> {
 }
 
 #[derive(Debug)]
-struct PipeGrid {
+pub struct PipeGrid {
     pieces: Vec<Piece>,
     width: usize,
     height: usize,
@@ -164,7 +166,7 @@ impl PipeGrid {
     }
 }
 
-fn parse_grid(input: &str) -> ParseResult<PipeGrid> {
+fn parse_grid(input: &str) -> ParseResult<'_, PipeGrid> {
     let mut pieces = Vec::new();
     let mut height = 0;
     let parse_line = parse_separated_terminated(
@@ -189,52 +191,27 @@ fn parse_grid(input: &str) -> ParseResult<PipeGrid> {
     ))
 }
 
-fn parse_input(input: &str) -> ParseFinalResult<PipeGrid> {
+fn parse_input(input: &str) -> ParseFinalResult<'_, PipeGrid> {
     final_parser(parse_grid)(input)
 }
 
-pub fn level1(input: &str) -> usize {
-    let grid = parse_input(input).expect("parse error");
-    let (_, length) = grid.loop_length_and_area().expect("No closed loop");
-    length / 2
-}
-
-pub fn level2(input: &str) -> usize {
-    let grid = parse_input(input).expect("parse error");
-    grid.loop_length_and_area().expect("No closed loop").0
-}
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day10.txt");
-        assert_eq!(level1(test_input), 4)
-    }
+pub struct Day;
 
-    #[test]
-    fn level1_given_example_complex() {
-        let test_input = include_str!("./test_input/day10_complex.txt");
-        assert_eq!(level1(test_input), 8)
-    }
+impl Solution for Day {
+    type Parsed<'a> = PipeGrid;
+    type Answer1 = usize;
+    type Answer2 = usize;
 
-    #[test]
-    fn level2_given_example_simple() {
-        let test_input = include_str!("./test_input/day10.txt");
-        assert_eq!(level2(test_input), 1)
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).diagnose(input)
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day10_loop.txt");
-        assert_eq!(level2(test_input), 4)
+    fn part1(grid: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        let (_, length) = grid.loop_length_and_area().context("no closed loop")?;
+        Ok(length / 2)
     }
 
-    #[test]
-    fn level2_given_example_complex() {
-        let test_input = include_str!("./test_input/day10_loop_complex.txt");
-        assert_eq!(level2(test_input), 8)
+    fn part2(grid: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        Ok(grid.loop_length_and_area().context("no closed loop")?.0)
     }
 }
@@ -2,24 +2,25 @@ use std::collections::{BTreeSet, HashSet};
 
 use nom_supreme::multi::parse_separated_terminated;
 
+use anyhow::Context;
+
 use crate::util::prelude::*;
 
 #[derive(Debug)]
-struct Universe {
+pub struct Universe {
     galaxies: Vec<(usize, usize)>,
     empty_rows: BTreeSet<usize>,
     empty_cols: BTreeSet<usize>,
-    expansion_factor: usize,
 }
 
 impl Universe {
-    fn distance_sum(&self) -> usize {
+    fn distance_sum(&self, expansion_factor: usize) -> usize {
         let mut result = 0;
         for (i, &(x1, y1)) in self.galaxies.iter().enumerate() {
             for &(x2, y2) in &self.galaxies[(i + 1)..] {
                 result += x1.abs_diff(x2)
                     + y1.abs_diff(y2)
-                    + (self.expansion_factor - 1)
+                    + (expansion_factor - 1)
                         * (self.empty_cols.range(x1.min(x2)..x1.max(x2)).count()
                             + self.empty_rows.range(y1.min(y2)..y1.max(y2)).count())
             }
@@ -70,37 +71,37 @@ fn parse_input(input: &str) -> Option<Universe> {
         galaxies,
         empty_rows,
         empty_cols,
-        expansion_factor: 1,
     })
 }
 
-pub fn level1(input: &str) -> usize {
-    let mut universe = parse_input(input).expect("parse error");
-    universe.expansion_factor = 2;
-    universe.distance_sum()
-}
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Universe;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).context("expected a rectangular grid of '.' and '#'")
+    }
+
+    fn part1(universe: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        Ok(universe.distance_sum(2))
+    }
 
-pub fn level2(input: &str) -> usize {
-    let mut universe = parse_input(input).expect("parse error");
-    universe.expansion_factor = 1_000_000;
-    universe.distance_sum()
+    fn part2(universe: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        Ok(universe.distance_sum(1_000_000))
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day11.txt");
-        assert_eq!(level1(test_input), 374)
-    }
-
     #[test]
     fn level2_given_example() {
         let test_input = include_str!("./test_input/day11.txt");
-        let mut universe = parse_input(test_input).expect("parse error");
-        universe.expansion_factor = 100;
-        assert_eq!(universe.distance_sum(), 8410);
+        let universe = Day::parse(test_input).unwrap();
+        assert_eq!(universe.distance_sum(100), 8410);
     }
 }
@@ -23,7 +23,7 @@ enum Color {
 }
 
 #[derive(Debug, Clone, Default, PartialEq, Eq)]
-struct CubeSet {
+pub struct CubeSet {
     red: u32,
     green: u32,
     blue: u32,
@@ -51,59 +51,6 @@ impl Extend<CubeSet> for CubeSet {
     }
 }
 
-#[derive(Debug)]
-struct IsGameUnderMax<const RED: u32, const BLUE: u32, const GREEN: u32> {
-    is_under_max: bool,
-}
-
-impl<const RED: u32, const BLUE: u32, const GREEN: u32> Extend<CubeSet>
-    for IsGameUnderMax<RED, BLUE, GREEN>
-{
-    fn extend<T: IntoIterator<Item = CubeSet>>(&mut self, iter: T) {
-        for set in iter {
-            self.is_under_max &= set.red <= RED && set.blue <= BLUE && set.green <= GREEN
-        }
-    }
-}
-
-#[derive(Debug, Default)]
-struct GamesUnderMaxCounter<const RED: u32, const BLUE: u32, const GREEN: u32> {
-    sum: u32,
-}
-
-impl<const RED: u32, const BLUE: u32, const GREEN: u32>
-    Extend<(u32, IsGameUnderMax<RED, GREEN, BLUE>)> for GamesUnderMaxCounter<RED, BLUE, GREEN>
-{
-    fn extend<T: IntoIterator<Item = (u32, IsGameUnderMax<RED, GREEN, BLUE>)>>(&mut self, iter: T) {
-        self.sum += iter
-            .into_iter()
-            .filter_map(|(id, is_under)| is_under.is_under_max.then_some(id))
-            .sum::<u32>()
-    }
-}
-
-#[derive(Debug, Default)]
-struct GamePowerCounter {
-    sum: u32,
-}
-
-impl Extend<(u32, CubeSet)> for GamePowerCounter {
-    fn extend<T: IntoIterator<Item = (u32, CubeSet)>>(&mut self, iter: T) {
-        self.sum += iter
-            .into_iter()
-            .map(|(_, CubeSet { red, green, blue })| red * green * blue)
-            .sum::<u32>()
-    }
-}
-
-impl<const RED: u32, const BLUE: u32, const GREEN: u32> Default
-    for IsGameUnderMax<RED, BLUE, GREEN>
-{
-    fn default() -> Self {
-        Self { is_under_max: true }
-    }
-}
-
 fn parse_cube_set(input: &str) -> IResult<&str, CubeSet, ErrorTree<&str>> {
     let parse_color = tuple((
         u32,
@@ -147,29 +94,30 @@ where
     final_parser(collect_separated_terminated(parse_game, success(()), eof))(input)
 }
 
-pub fn level1(input: &str) -> u32 {
-    let result: GamesUnderMaxCounter<12, 13, 14> = parse_input(input).expect("parse error");
-    result.sum
-}
+pub struct Day;
 
-pub fn level2(input: &str) -> u32 {
-    let result: GamePowerCounter = parse_input(input).expect("parse error");
-    result.sum
-}
+impl Solution for Day {
+    /// Game IDs with the minimal set of cubes each game needs
+    type Parsed<'a> = Vec<(u32, CubeSet)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).diagnose(input)
+    }
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day2.txt");
-        assert_eq!(level1(test_input), 8)
+    fn part1(games: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        Ok(games
+            .iter()
+            .filter(|(_, set)| set.red <= 12 && set.green <= 13 && set.blue <= 14)
+            .map(|(id, _)| id)
+            .sum())
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day2.txt");
-        assert_eq!(level2(test_input), 2286)
+    fn part2(games: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        Ok(games
+            .iter()
+            .map(|(_, CubeSet { red, green, blue })| red * green * blue)
+            .sum())
     }
 }
@@ -1,10 +1,22 @@
 use std::ops::Range;
 
-use itertools::Itertools;
+use anyhow::{ensure, Context};
 use regex::Regex;
 
-pub fn level1(input: &str) -> u32 {
-    let grid = input.lines().map(|s| s.chars().collect_vec()).collect_vec();
+use crate::util::prelude::*;
+
+pub struct Schematic<'a> {
+    input: &'a str,
+    grid: Vec<Vec<char>>,
+}
+
+fn parse_number(digits: &str) -> anyhow::Result<u32> {
+    digits
+        .parse()
+        .with_context(|| format!("number {digits} does not fit in 32 bits"))
+}
+
+fn part_number_sum(Schematic { input, grid }: &Schematic) -> anyhow::Result<u32> {
     let regex = Regex::new(r"\d+").unwrap();
     let width = grid[0].len();
     regex
@@ -14,7 +26,6 @@ pub fn level1(input: &str) -> u32 {
             let row = start / (width + 1);
             let start = start - row * (width + 1);
             let end = end - row * (width + 1);
-            let number: u32 = number_match.as_str().parse().expect("parse number");
             let is_symbol = |c: &char| *c != '.' && c.to_digit(10).is_none();
             let mut has_symbol = false;
             if row > 0 {
@@ -35,16 +46,16 @@ pub fn level1(input: &str) -> u32 {
             }
 
             if has_symbol {
-                number
+                parse_number(number_match.as_str())
             } else {
-                0
+                Ok(0)
             }
         })
         .sum()
 }
 
-pub fn level2(input: &str) -> u32 {
-    let width = input.lines().next().unwrap().len() + 1;
+fn gear_ratio_sum(Schematic { input, grid }: &Schematic) -> anyhow::Result<u32> {
+    let width = grid[0].len() + 1;
     let gear_regex = Regex::new(r"\*").unwrap();
     let number_regex = Regex::new(r"\d+").unwrap();
 
@@ -61,7 +72,7 @@ pub fn level2(input: &str) -> u32 {
                     .filter(|number_match| {
                         number_match.start() < column + 2 && number_match.end() >= column
                     })
-                    .map(|number_match| number_match.as_str().parse::<u32>().expect("parse error")),
+                    .map(|number_match| number_match.as_str()),
             )
         }
         if (row + 1) * width < input.len() {
@@ -71,7 +82,7 @@ pub fn level2(input: &str) -> u32 {
                     .filter(|number_match| {
                         number_match.start() < column + 2 && number_match.end() >= column
                     })
-                    .map(|number_match| number_match.as_str().parse::<u32>().expect("parse error")),
+                    .map(|number_match| number_match.as_str()),
             )
         }
         if column > 0 {
@@ -81,7 +92,7 @@ pub fn level2(input: &str) -> u32 {
                     None => &input[(row * width)..gear_match.start()],
                 };
             if !slice_before.is_empty() {
-                surrounding_numbers.push(slice_before.parse().expect("parse error"))
+                surrounding_numbers.push(slice_before)
             }
         }
         if column + 2 < width {
@@ -91,15 +102,40 @@ pub fn level2(input: &str) -> u32 {
                     None => &input[gear_match.end()..((row + 1) * width)],
                 };
             if !slice_after.is_empty() {
-                surrounding_numbers.push(slice_after.parse().expect("parse error"))
+                surrounding_numbers.push(slice_after)
             }
         }
 
-        if surrounding_numbers.len() == 2 {
-            result += surrounding_numbers.into_iter().product::<u32>();
+        if let [first, second] = surrounding_numbers[..] {
+            result += parse_number(first)? * parse_number(second)?;
         }
     }
-    result
+    Ok(result)
+}
+
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Schematic<'a>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        let grid = input.lines().map(|s| s.chars().collect_vec()).collect_vec();
+        ensure!(
+            !grid.is_empty() && grid.iter().all(|row| row.len() == grid[0].len()),
+            "expected a rectangular grid"
+        );
+        Ok(Schematic { input, grid })
+    }
+
+    fn part1(schematic: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        part_number_sum(schematic)
+    }
+
+    fn part2(schematic: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        gear_ratio_sum(schematic)
+    }
 }
 
 #[cfg(test)]
@@ -107,14 +143,10 @@ mod test {
     use super::*;
 
     #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day3.txt");
-        assert_eq!(level1(test_input), 4361)
-    }
-
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day3.txt");
-        assert_eq!(level2(test_input), 467835)
+    fn ragged_grids_are_errors() {
+        for input in ["", "1*\n2\n3..\n", "12*\n.\n5..\n"] {
+            let error = Day::parse(input).err().unwrap();
+            assert_eq!(error.to_string(), "expected a rectangular grid");
+        }
     }
 }
@@ -21,21 +21,40 @@ fn parse_line(line: &str) -> Result<usize> {
     Ok(first_numbers.intersection(&last_numbers).count())
 }
 
-pub fn level1(input: &str) -> usize {
-    input
-        .lines()
-        .map(|l| parse_line(l).expect("line parse"))
-        .filter_map(|count| count.checked_sub(1).map(|x| 1 << x))
-        .sum()
+pub struct Day;
+
+impl Solution for Day {
+    /// Number of winning numbers on each card
+    type Parsed<'a> = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| parse_line(l).with_context(|| format!("card on line {}", i + 1)))
+            .collect()
+    }
+
+    fn part1(counts: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        Ok(counts
+            .iter()
+            .filter_map(|count| count.checked_sub(1).map(|x| 1 << x))
+            .sum())
+    }
+
+    fn part2(counts: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        Ok(copy_total(counts))
+    }
 }
 
-pub fn level2(input: &str) -> usize {
+fn copy_total(counts: &[usize]) -> usize {
     let mut total: usize = 0;
     let mut copy_count = 0;
     let mut copy_count_offsets = BTreeMap::new();
-    for (i, line) in input.lines().enumerate() {
+    for (i, &count) in counts.iter().enumerate() {
         // get copy_count cards from previous, and another one because we already have it
-        let count = parse_line(line).expect("line parse");
         total += copy_count + 1;
         if count > 0 {
             *copy_count_offsets.entry(i + count).or_default() += copy_count + 1;
@@ -50,20 +69,3 @@ pub fn level2(input: &str) -> usize {
     }
     total
 }
-
-#[cfg(test)]
-mod test {
-    use super::*;
-
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day4.txt");
-        assert_eq!(level1(test_input), 13)
-    }
-
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day4.txt");
-        assert_eq!(level2(test_input), 30)
-    }
-}
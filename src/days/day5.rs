@@ -4,6 +4,7 @@ use std::{
     iter::once,
 };
 
+use anyhow::{ensure, Context};
 use nom::{
     character::{
         complete::{char, newline, space1, u64},
@@ -27,7 +28,7 @@ struct Segment {
 }
 
 #[derive(Debug, Default)]
-struct RangeMap(BTreeMap<usize, Segment>);
+pub struct RangeMap(BTreeMap<usize, Segment>);
 
 impl RangeMap {
     fn get(&self, x: usize) -> Option<usize> {
@@ -45,20 +46,20 @@ impl Extend<(usize, usize, usize)> for RangeMap {
     }
 }
 
-fn parse_usize(input: &str) -> ParseResult<usize> {
+fn parse_usize(input: &str) -> ParseResult<'_, usize> {
     map_opt(u64, |x| -> Option<usize> { x.try_into().ok() })
         .context("usize")
         .parse(input)
 }
 
-fn parse_seeds(input: &str) -> ParseResult<Vec<usize>> {
+fn parse_seeds(input: &str) -> ParseResult<'_, Vec<usize>> {
     collect_separated_terminated(parse_usize, space1, newline)
         .preceded_by(tag("seeds: "))
         .context("seeds")
         .parse(input)
 }
 
-fn parse_block_description(input: &str) -> ParseResult<(&str, &str)> {
+fn parse_block_description(input: &str) -> ParseResult<'_, (&str, &str)> {
     separated_pair(alpha1, tag("-to-"), alpha1)
         .terminated(tag(" map:"))
         .terminated(newline)
@@ -66,7 +67,7 @@ fn parse_block_description(input: &str) -> ParseResult<(&str, &str)> {
         .parse(input)
 }
 
-fn parse_block(input: &str) -> ParseResult<RangeMap> {
+fn parse_block(input: &str) -> ParseResult<'_, RangeMap> {
     collect_separated_terminated(
         tuple((parse_usize, space1, parse_usize, space1, parse_usize)).map(|t| (t.0, t.2, t.4)),
         newline,
@@ -76,9 +77,9 @@ fn parse_block(input: &str) -> ParseResult<RangeMap> {
     .parse(input)
 }
 
-type MapConfig<'a> = (Vec<usize>, HashMap<&'a str, (&'a str, RangeMap)>);
+pub type MapConfig<'a> = (Vec<usize>, HashMap<&'a str, (&'a str, RangeMap)>);
 
-fn parse_input(input: &str) -> ParseFinalResult<MapConfig> {
+fn parse_input(input: &str) -> ParseFinalResult<'_, MapConfig<'_>> {
     final_parser(tuple((
         parse_seeds.terminated(newline),
         collect_separated_terminated(
@@ -91,8 +92,8 @@ fn parse_input(input: &str) -> ParseFinalResult<MapConfig> {
     )))(input)
 }
 
-pub fn level1(input: &str) -> usize {
-    let (mut offsets, maps) = parse_input(input).expect("parse error");
+fn lowest_location((seeds, maps): &MapConfig) -> anyhow::Result<usize> {
+    let mut offsets = seeds.clone();
     let mut map_type = "seed";
     while let Some((new_map_type, map)) = maps.get(map_type) {
         offsets = offsets
@@ -101,8 +102,8 @@ pub fn level1(input: &str) -> usize {
             .collect();
         map_type = new_map_type
     }
-    assert_eq!(map_type, "location");
-    offsets.into_iter().min().expect("empty offsets")
+    ensure!(map_type == "location", "the maps end at {map_type}, not location");
+    offsets.into_iter().min().context("there are no seeds")
 }
 
 #[derive(Default, Debug)]
@@ -167,8 +168,7 @@ impl Intervals {
     }
 }
 
-pub fn level2(input: &str) -> usize {
-    let (seeds, maps) = parse_input(input).expect("parse error");
+fn lowest_location_of_ranges((seeds, maps): &MapConfig) -> anyhow::Result<usize> {
     let mut ranges: Intervals = seeds
         .chunks_exact(2)
         .map(|chunk| match chunk {
@@ -201,24 +201,31 @@ pub fn level2(input: &str) -> usize {
         ranges = new_ranges;
         map_type = *new_map_type;
     }
-    debug_assert_eq!(map_type, "location");
+    ensure!(map_type == "location", "the maps end at {map_type}, not location");
 
-    ranges.0[0].0
+    ranges
+        .0
+        .first()
+        .map(|&(start, _)| start)
+        .context("there are no seed ranges")
 }
 
-#[cfg(test)]
-mod test {
-    use super::*;
+pub struct Day;
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day5.txt");
-        assert_eq!(level1(test_input), 35)
+impl Solution for Day {
+    type Parsed<'a> = MapConfig<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).diagnose(input)
+    }
+
+    fn part1(config: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        lowest_location(config)
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day5.txt");
-        assert_eq!(level2(test_input), 46)
+    fn part2(config: &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        lowest_location_of_ranges(config)
     }
 }
@@ -1,40 +1,62 @@
 use std::iter::zip;
 
+use anyhow::{bail, Context};
+
 use crate::util::prelude::*;
 
-fn do_level2(input: &str) -> Option<u64> {
-    let (time_line, dist_line) = input.split_once('\n')?;
-    let t: String = time_line
-        .strip_prefix("Time:")?
+/// The races, and the digit groups of both lines kept as text since level 2 joins them
+pub struct Races<'a> {
+    races: Vec<(u64, u64)>,
+    times: Vec<&'a str>,
+    distances: Vec<&'a str>,
+}
+
+fn parse_number(digits: &str, name: &str) -> anyhow::Result<u64> {
+    digits
+        .parse()
+        .with_context(|| format!("invalid {name} {digits:?}"))
+}
+
+fn parse_races(input: &str) -> anyhow::Result<Races<'_>> {
+    let (time_line, dist_line) = input
+        .split_once('\n')
+        .context("expected a line of times and a line of distances")?;
+    let times = time_line
+        .strip_prefix("Time:")
+        .context("expected the first line to start with \"Time:\"")?
         .split_whitespace()
-        .collect();
-    let d: String = dist_line
-        .strip_prefix("Distance:")?
+        .collect_vec();
+    let distances = dist_line
+        .strip_prefix("Distance:")
+        .context("expected the second line to start with \"Distance:\"")?
         .split_whitespace()
-        .collect();
+        .collect_vec();
+    if times.len() != distances.len() {
+        bail!("{} times but {} distances", times.len(), distances.len());
+    }
+    let races = zip(&times, &distances)
+        .map(|(t, d)| Ok((parse_number(t, "time")?, parse_number(d, "distance")?)))
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Races {
+        races,
+        times,
+        distances,
+    })
+}
 
-    number_of_possible_times((
-        t.parse().expect("time parse"),
-        d.parse().expect("distance parse"),
-    ))
+fn do_level2(races: &Races) -> anyhow::Result<u64> {
+    let t = parse_number(&races.times.concat(), "joined time")?;
+    let d = parse_number(&races.distances.concat(), "joined distance")?;
+    number_of_possible_times((t, d)).context("no winning time")
 }
 
-fn do_level1(input: &str) -> Option<u64> {
-    let (time_line, dist_line) = input.split_once('\n')?;
-    let ts = time_line.strip_prefix("Time:")?.split_whitespace();
-    let ds = dist_line.strip_prefix("Distance:")?.split_whitespace();
-
-    Some(
-        zip(ts, ds)
-            .map(|(t, d)| {
-                (
-                    t.parse().expect("time parse"),
-                    d.parse().expect("distance parse"),
-                )
-            })
-            .filter_map(number_of_possible_times)
-            .product(),
-    )
+fn do_level1(races: &Races) -> u64 {
+    races
+        .races
+        .iter()
+        .copied()
+        .filter_map(number_of_possible_times)
+        .product()
 }
 
 fn number_of_possible_times((t, d): (u64, u64)) -> Option<u64> {
@@ -42,7 +64,7 @@ fn number_of_possible_times((t, d): (u64, u64)) -> Option<u64> {
     // The distance between the roots is √(t² - 4d)
     // The rest is bookkeeping for finding the integer points: 2 less if the roots are integers,
     // an even amount iff t is odd
-    let disc = (t * t).checked_sub(4 * d)? as f64;
+    let disc = (u128::from(t) * u128::from(t)).checked_sub(4 * u128::from(d))? as f64;
     let even_offset = if t % 2 == 0 { 0.5 } else { 0.0 };
     if disc == 0.0 {
         return Some((2.0 * even_offset) as u64);
@@ -51,12 +73,24 @@ fn number_of_possible_times((t, d): (u64, u64)) -> Option<u64> {
     Some((2.0 * num_of_things).floor() as u64)
 }
 
-pub fn level1(input: &str) -> u64 {
-    do_level1(input).expect("parse error")
-}
+pub struct Day;
 
-pub fn level2(input: &str) -> u64 {
-    do_level2(input).expect("parse error")
+impl Solution for Day {
+    type Parsed<'a> = Races<'a>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_races(input)
+    }
+
+    fn part1(races: &Self::Parsed<'_>) -> anyhow::Result<u64> {
+        Ok(do_level1(races))
+    }
+
+    fn part2(races: &Self::Parsed<'_>) -> anyhow::Result<u64> {
+        do_level2(races)
+    }
 }
 
 #[cfg(test)]
@@ -64,14 +98,15 @@ mod test {
     use super::*;
 
     #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day6.txt");
-        assert_eq!(level1(test_input), 288)
-    }
+    fn bad_numbers_are_errors() {
+        let error = Day::parse("Time: 7 x\nDistance: 9 40\n").err().unwrap();
+        assert_eq!(error.to_string(), "invalid time \"x\"");
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day6.txt");
-        assert_eq!(level2(test_input), 71503)
+        let races = Day::parse("Time: 9999999999 9999999999\nDistance: 1 1\n").unwrap();
+        let error = Day::part2(&races).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "invalid joined time \"99999999999999999999\""
+        );
     }
 }
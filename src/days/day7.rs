@@ -14,7 +14,7 @@ use nom_supreme::{
 use crate::util::prelude::*;
 
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Hash, Clone, Copy)]
-enum Card {
+pub enum Card {
     Joker,
     Number(u8),
     Ten,
@@ -62,45 +62,7 @@ impl Hand {
     }
 }
 
-fn parse_card_with_jokers(input: &str) -> ParseResult<Card> {
-    map_opt(anychar, |c| {
-        c.to_digit(10)
-            .filter(|x| (2..=9).contains(x))
-            .map(|x| Card::Number(x as u8))
-    })
-    .context("Number card")
-    .or(alt((
-        tag("T").value(Card::Ten),
-        tag("J").value(Card::Joker),
-        tag("Q").value(Card::Queen),
-        tag("K").value(Card::King),
-        tag("A").value(Card::Ace),
-    ))
-    .context("Face card"))
-    .parse(input)
-}
-
-fn parse_hand_with_jokers(input: &str) -> ParseResult<Hand> {
-    separated_pair(
-        parse_card_with_jokers.separated_array(success(())),
-        space1,
-        u32,
-    )
-    .map(|(cards, bid)| Hand::new_with_jokers(cards, bid))
-    .terminated(newline)
-    .context("Hand")
-    .parse(input)
-}
-
-fn parse_input_with_jokers(input: &str) -> ParseFinalResult<Vec<Hand>> {
-    final_parser(collect_separated_terminated(
-        parse_hand_with_jokers,
-        success(()),
-        eof,
-    ))(input)
-}
-
-fn parse_card(input: &str) -> ParseResult<Card> {
+fn parse_card(input: &str) -> ParseResult<'_, Card> {
     map_opt(anychar, |c| {
         c.to_digit(10)
             .filter(|x| (2..=9).contains(x))
@@ -118,20 +80,18 @@ fn parse_card(input: &str) -> ParseResult<Card> {
     .parse(input)
 }
 
-fn parse_hand(input: &str) -> ParseResult<Hand> {
+fn parse_hand(input: &str) -> ParseResult<'_, ([Card; 5], u32)> {
     separated_pair(parse_card.separated_array(success(())), space1, u32)
-        .map(|(cards, bid)| Hand::new(cards, bid))
         .terminated(newline)
         .context("Hand")
         .parse(input)
 }
 
-fn parse_input(input: &str) -> ParseFinalResult<Vec<Hand>> {
+fn parse_input(input: &str) -> ParseFinalResult<'_, Vec<([Card; 5], u32)>> {
     final_parser(collect_separated_terminated(parse_hand, success(()), eof))(input)
 }
 
-pub fn level1(input: &str) -> u32 {
-    let mut hands = parse_input(input).expect("parse error");
+fn total_winnings(mut hands: Vec<Hand>) -> u32 {
     hands.sort();
     hands
         .into_iter()
@@ -140,29 +100,39 @@ pub fn level1(input: &str) -> u32 {
         .sum()
 }
 
-pub fn level2(input: &str) -> u32 {
-    let mut hands = parse_input_with_jokers(input).expect("parse error");
-    hands.sort();
-    hands
-        .into_iter()
-        .enumerate()
-        .map(|(i, hand)| (i as u32 + 1) * hand.bid)
-        .sum()
-}
+pub struct Day;
+
+impl Solution for Day {
+    /// Cards and bids, with every `J` read as a jack
+    type Parsed<'a> = Vec<([Card; 5], u32)>;
+    type Answer1 = u32;
+    type Answer2 = u32;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).diagnose(input)
+    }
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day7.txt");
-        assert_eq!(level1(test_input), 6440)
+    fn part1(hands: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        Ok(total_winnings(
+            hands
+                .iter()
+                .map(|&(cards, bid)| Hand::new(cards, bid))
+                .collect(),
+        ))
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day7.txt");
-        assert_eq!(level2(test_input), 5905)
+    fn part2(hands: &Self::Parsed<'_>) -> anyhow::Result<u32> {
+        Ok(total_winnings(
+            hands
+                .iter()
+                .map(|&(cards, bid)| {
+                    let cards = cards.map(|card| match card {
+                        Card::Jack => Card::Joker,
+                        card => card,
+                    });
+                    Hand::new_with_jokers(cards, bid)
+                })
+                .collect(),
+        ))
     }
 }
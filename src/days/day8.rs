@@ -14,10 +14,12 @@ use nom_supreme::{
     final_parser::final_parser, multi::collect_separated_terminated, tag::complete::tag, ParserExt,
 };
 
+use anyhow::{ensure, Context};
+
 use crate::util::prelude::*;
 
 #[derive(Debug, Clone, Copy)]
-enum Direction {
+pub enum Direction {
     Left,
     Right,
 }
@@ -276,7 +278,7 @@ impl<'a> Graph<'a> {
     }
 }
 
-fn parse_directions(input: &str) -> ParseResult<Vec<Direction>> {
+fn parse_directions(input: &str) -> ParseResult<'_, Vec<Direction>> {
     collect_separated_terminated(
         alt((
             tag("L").value(Direction::Left),
@@ -288,7 +290,7 @@ fn parse_directions(input: &str) -> ParseResult<Vec<Direction>> {
     .parse(input)
 }
 
-fn parse_node(input: &str) -> ParseResult<(&str, (&str, &str))> {
+fn parse_node(input: &str) -> ParseResult<'_, (&str, (&str, &str))> {
     separated_pair(
         alphanumeric1.context("source"),
         tag(" = ("),
@@ -301,9 +303,9 @@ fn parse_node(input: &str) -> ParseResult<(&str, (&str, &str))> {
     )(input)
 }
 
-type Input<'a> = (Vec<Direction>, HashMap<&'a str, (&'a str, &'a str)>);
+pub type Input<'a> = (Vec<Direction>, HashMap<&'a str, (&'a str, &'a str)>);
 
-fn parse_input(input: &str) -> ParseFinalResult<Input> {
+fn parse_input(input: &str) -> ParseFinalResult<'_, Input<'_>> {
     final_parser(
         parse_directions
             .terminated(newline)
@@ -311,22 +313,42 @@ fn parse_input(input: &str) -> ParseFinalResult<Input> {
     )(input)
 }
 
-pub fn level1(input: &str) -> usize {
-    let (directions, mappings) = parse_input(input).expect("parse error");
-    let graph = Graph::new(&directions, &mappings, |s| s == "ZZZ");
-    let cycle = graph.cycle("AAA");
-    cycle.first().expect("target is unreachable")
-}
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Input<'a>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        let (directions, mappings) = parse_input(input).diagnose(input)?;
+        for (source, (left, right)) in &mappings {
+            for target in [left, right] {
+                ensure!(
+                    mappings.contains_key(target),
+                    "{source} leads to undefined node {target}"
+                );
+            }
+        }
+        Ok((directions, mappings))
+    }
 
-pub fn level2(input: &str) -> usize {
-    let (directions, mappings) = parse_input(input).expect("parse error");
-    let graph = Graph::new(&directions, &mappings, |s| s.ends_with('Z'));
-    let cycle = mappings
-        .keys()
-        .filter(|s| s.ends_with('A'))
-        .map(|s| graph.cycle(s))
-        .fold(Cycle::all_hits(), |c1, c2| c1.intersect(c2));
-    cycle.first().expect("target state is unreachable")
+    fn part1((directions, mappings): &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        ensure!(mappings.contains_key("AAA"), "there is no node AAA");
+        let graph = Graph::new(directions, mappings, |s| s == "ZZZ");
+        let cycle = graph.cycle("AAA");
+        cycle.first().context("target is unreachable")
+    }
+
+    fn part2((directions, mappings): &Self::Parsed<'_>) -> anyhow::Result<usize> {
+        let graph = Graph::new(directions, mappings, |s| s.ends_with('Z'));
+        let cycle = mappings
+            .keys()
+            .filter(|s| s.ends_with('A'))
+            .map(|s| graph.cycle(s))
+            .fold(Cycle::all_hits(), |c1, c2| c1.intersect(c2));
+        cycle.first().context("target state is unreachable")
+    }
 }
 
 #[cfg(test)]
@@ -334,20 +356,12 @@ mod test {
     use super::*;
 
     #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day8.txt");
-        assert_eq!(level1(test_input), 2)
-    }
+    fn missing_nodes_are_errors() {
+        let error = Day::parse("LR\n\nAAA = (BBB, BBB)\n").err().unwrap();
+        assert_eq!(error.to_string(), "AAA leads to undefined node BBB");
 
-    #[test]
-    fn level1_given_example_long() {
-        let test_input = include_str!("./test_input/day8_long.txt");
-        assert_eq!(level1(test_input), 6)
-    }
-
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day8_ghost.txt");
-        assert_eq!(level2(test_input), 6)
+        let network = Day::parse("LR\n\nBBB = (BBB, BBB)\n").unwrap();
+        let error = Day::part1(&network).unwrap_err();
+        assert_eq!(error.to_string(), "there is no node AAA");
     }
 }
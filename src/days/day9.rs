@@ -14,7 +14,7 @@ use nom_supreme::{
 use crate::util::prelude::*;
 
 #[derive(Debug, Default)]
-struct SequencePredictor {
+pub struct SequencePredictor {
     coefficients: Vec<i64>,
 }
 
@@ -44,45 +44,34 @@ impl SequencePredictor {
     }
 }
 
-fn parse_predictor(input: &str) -> ParseResult<SequencePredictor> {
+fn parse_predictor(input: &str) -> ParseResult<'_, SequencePredictor> {
     collect_separated_terminated(i64, space1, tag("\n")).parse(input)
 }
 
-pub fn level1(input: &str) -> i64 {
-    let result: ParseFinalResult<_> = final_parser(parse_separated_terminated(
+fn parse_input(input: &str) -> ParseFinalResult<'_, Vec<SequencePredictor>> {
+    final_parser(collect_separated_terminated(
         parse_predictor,
         success(()),
         eof,
-        || 0_i64,
-        |x, predict| x + predict.predict_next(),
-    ))(input);
-    result.expect("parse error")
+    ))(input)
 }
 
-pub fn level2(input: &str) -> i64 {
-        let result: ParseFinalResult<_> = final_parser(parse_separated_terminated(
-        parse_predictor,
-        success(()),
-        eof,
-        || 0_i64,
-        |x, predict| x + predict.predict(-1),
-    ))(input);
-    result.expect("parse error")
-}
+pub struct Day;
+
+impl Solution for Day {
+    type Parsed<'a> = Vec<SequencePredictor>;
+    type Answer1 = i64;
+    type Answer2 = i64;
 
-#[cfg(test)]
-mod test {
-    use super::*;
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>> {
+        parse_input(input).diagnose(input)
+    }
 
-    #[test]
-    fn level1_given_example() {
-        let test_input = include_str!("./test_input/day9.txt");
-        assert_eq!(level1(test_input), 114)
+    fn part1(predictors: &Self::Parsed<'_>) -> anyhow::Result<i64> {
+        Ok(predictors.iter().map(SequencePredictor::predict_next).sum())
     }
 
-    #[test]
-    fn level2_given_example() {
-        let test_input = include_str!("./test_input/day9.txt");
-        assert_eq!(level2(test_input), 2)
+    fn part2(predictors: &Self::Parsed<'_>) -> anyhow::Result<i64> {
+        Ok(predictors.iter().map(|predictor| predictor.predict(-1)).sum())
     }
 }
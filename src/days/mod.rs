@@ -1,13 +1,67 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
-pub mod day1;
-pub mod day2;
-pub mod day3;
-pub mod day4;
-pub mod day5;
-pub mod day6;
-pub mod day7;
-pub mod day8;
-pub mod day9;
-pub mod day10;
-pub mod day11;
+use crate::util::registry::register_days;
+
+register_days! {
+    1 => day1,
+    2 => day2,
+    3 => day3,
+    4 => day4,
+    5 => day5,
+    6 => day6,
+    7 => day7,
+    8 => day8,
+    9 => day9,
+    10 => day10,
+    11 => day11,
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::Path;
+
+    use crate::util::{
+        examples::{check_examples, Manifest},
+        infra::TEST_INPUT_DIR,
+    };
+
+    #[test]
+    fn given_examples() {
+        let manifest = Manifest::load_default().unwrap();
+        let failures = check_examples(&manifest);
+        assert!(
+            failures.is_empty(),
+            "{} example(s) failed:\n{}",
+            failures.len(),
+            failures.join("\n")
+        );
+    }
+
+    /// Every day goes through input normalization, so editors and git settings do not matter.
+    #[test]
+    fn examples_with_mangled_line_endings() {
+        let manifest = Manifest::load_default().unwrap();
+        let mut failures = Vec::new();
+        for expectation in &manifest.expectations {
+            let solver = super::get(expectation.day().unwrap()).unwrap();
+            let path = Path::new(TEST_INPUT_DIR).join(format!("{}.txt", expectation.file));
+            let input = std::fs::read_to_string(path).unwrap();
+            let variants = [
+                ("CRLF", input.replace('\n', "\r\n")),
+                ("no final newline", input.trim_end().to_string()),
+                ("extra blank lines", format!("\n{}\n\n\n", input.trim_end())),
+            ];
+            for (name, variant) in variants {
+                let answer = (solver.run)(&variant, &[expectation.level])
+                    .map(|mut run| run.levels.remove(0).answer);
+                if !matches!(&answer, Ok(answer) if *answer == expectation.answer) {
+                    failures.push(format!(
+                        "{} level {} with {name}: {answer:?}",
+                        expectation.file, expectation.level
+                    ));
+                }
+            }
+        }
+        assert!(failures.is_empty(), "{}", failures.join("\n"));
+    }
+}
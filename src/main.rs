@@ -0,0 +1,429 @@
+use anyhow::{anyhow, bail, Context};
+use std::{path::Path, time::Duration};
+
+use aoc::{
+    days,
+    util::{
+        alloc::AllocStats,
+        bench::{bench_day, regressions, BenchReport},
+        examples::{Expectation, Manifest, Status},
+        export::{Export, Report},
+        infra::*,
+        prelude::Itertools,
+        registry::Solver,
+        runner::{run_parallel, Row},
+        watch::{check_day, wait_for_change, watched_paths, Snapshot},
+    },
+};
+
+fn main() -> anyhow::Result<()> {
+    match parse_args()? {
+        Command::Run {
+            days: selection,
+            level,
+            input: source,
+            submit: should_submit,
+            wait,
+            export,
+        } => {
+            let levels = match level {
+                Some(level) => vec![level],
+                None => Level::ALL.to_vec(),
+            };
+            let solvers = match selection {
+                Days::All => days::DAYS.iter().collect(),
+                Days::One(day) => {
+                    vec![days::get(day).ok_or_else(|| anyhow!("Day {day} is not registered"))?]
+                }
+            };
+            if matches!(selection, Days::All) || export.is_some() {
+                return run_table(&solvers, &levels, export.as_ref());
+            }
+            let solver = solvers[0];
+            let input = source.load(solver.day)?;
+            let run =
+                (solver.run)(&input, &levels).with_context(|| format!("day {}", solver.day))?;
+            println!(
+                "day {} parse: {:?}{}",
+                solver.day,
+                run.parse_time,
+                alloc_summary(run.parse_alloc)
+            );
+            for level_run in run.levels {
+                println!(
+                    "day {} level {}: {} ({:?}{})",
+                    solver.day,
+                    level_run.level,
+                    level_run.answer,
+                    level_run.time,
+                    alloc_summary(level_run.alloc)
+                );
+                if should_submit.is_some() {
+                    let mut history = History::load(HISTORY_PATH)?;
+                    let outcome = submit(
+                        &ClientConfig::from_env()?,
+                        solver.day,
+                        level_run.level,
+                        &level_run.answer,
+                        &mut history,
+                        &SystemClock,
+                        wait,
+                    )?;
+                    println!("{outcome}");
+                    if outcome == Outcome::Sent(Verdict::Correct) {
+                        Manifest::load_answers()?.add(Expectation {
+                            file: format!("day{}", solver.day),
+                            level: level_run.level,
+                            answer: level_run.answer.clone(),
+                        })?;
+                    }
+                    if let Outcome::Sent(Verdict::RateLimited { .. }) = outcome {
+                        if wait {
+                            eprintln!("the site kept asking to wait, giving up for now");
+                        } else {
+                            eprintln!("pass --wait to retry once the cooldown is over");
+                        }
+                    }
+                    if !outcome.is_success(&level_run.answer) {
+                        bail!("answer {} was not accepted", level_run.answer);
+                    }
+                }
+            }
+        }
+        Command::Bench {
+            days: selection,
+            iterations,
+            output,
+            baseline,
+            threshold,
+        } => {
+            let solvers = match selection {
+                Days::All => days::DAYS.iter().collect(),
+                Days::One(day) => {
+                    vec![days::get(day).ok_or_else(|| anyhow!("Day {day} is not registered"))?]
+                }
+            };
+            let mut report = BenchReport {
+                iterations,
+                days: Vec::new(),
+            };
+            println!(
+                "{:>3}  {:>7}  {:>12}  {:>12}  {:>12}",
+                "day", "phase", "min", "median", "mean"
+            );
+            for solver in solvers {
+                let Ok(input) = InputSource::Default.load(solver.day) else {
+                    eprintln!("day {}: missing input, skipped", solver.day);
+                    continue;
+                };
+                let day = bench_day(solver, &input, iterations)
+                    .with_context(|| format!("day {}", solver.day))?;
+                for (phase, stats) in day.phases() {
+                    println!(
+                        "{:>3}  {phase:>7}  {:>12?}  {:>12?}  {:>12?}",
+                        day.day,
+                        Duration::from_nanos(stats.min_ns),
+                        Duration::from_nanos(stats.median_ns),
+                        Duration::from_nanos(stats.mean_ns),
+                    );
+                }
+                report.days.push(day);
+            }
+            report.save(&output)?;
+            println!("results written to {}", output.display());
+            if let Some(baseline) = baseline {
+                let found = regressions(&BenchReport::load(baseline)?, &report, threshold);
+                for regression in &found {
+                    println!(
+                        "regression: day {} {} median {:?} -> {:?} (+{:.1}%)",
+                        regression.day,
+                        regression.phase,
+                        Duration::from_nanos(regression.baseline_ns),
+                        Duration::from_nanos(regression.current_ns),
+                        regression.percent(),
+                    );
+                }
+                if !found.is_empty() {
+                    bail!(
+                        "{} phase(s) regressed by more than {threshold}%",
+                        found.len()
+                    );
+                }
+            }
+        }
+        Command::Check { record } => {
+            if record {
+                let mut answers = Manifest::load_answers()?;
+                let history = History::load(HISTORY_PATH)?;
+                for solver in days::DAYS {
+                    for level in Level::ALL {
+                        let Some(answer) = history.accepted_answer(YEAR, solver.day, level) else {
+                            continue;
+                        };
+                        let added = answers.add(Expectation {
+                            file: format!("day{}", solver.day),
+                            level,
+                            answer: answer.clone(),
+                        })?;
+                        if added {
+                            println!("recorded {answer} for day {} level {level}", solver.day);
+                        }
+                    }
+                }
+            }
+            let solvers = days::DAYS.iter().collect_vec();
+            run_table(&solvers, &Level::ALL, None)?;
+        }
+        Command::Fetch { year, day } => {
+            let mut config = ClientConfig::from_env()?;
+            if let Some(year) = year {
+                config.year = year;
+            }
+            let input = fetch_input(&config, day)?;
+            println!("day {day}: {} lines of input", input.lines().count());
+        }
+        Command::Whoami => {
+            println!("logged in as {}", whoami(&ClientConfig::from_env()?)?);
+        }
+        Command::WaitAndFetch { year, day } => {
+            let mut config = ClientConfig::from_env()?;
+            if let Some(day) = day.filter(|day| !(1..=25).contains(day)) {
+                bail!("There is no puzzle for day {day}");
+            }
+            // Without a configured year, wait for the next event
+            let (year, day) = match (year.or(ClientConfig::year_from_env()?), day) {
+                (Some(year), Some(day)) => (year, day),
+                (Some(year), None) => {
+                    let day = next_unlock(year, SystemClock.now())
+                        .ok_or_else(|| anyhow!("Every puzzle of {year} is unlocked, pass --day"))?;
+                    (year, day)
+                }
+                (None, Some(day)) => (next_event_unlock(&SystemClock).0, day),
+                (None, None) => next_event_unlock(&SystemClock),
+            };
+            config.year = year;
+            // Better to find an expired session now than after the countdown
+            whoami(&config)?;
+            wait_for_unlock(&SystemClock, config.year, day);
+            let input = fetch_input(&config, day)?;
+            println!("day {day}: {} lines of input", input.lines().count());
+            if days::get(day).is_some() {
+                println!("day {day} is already registered");
+            } else {
+                scaffold_day(Path::new("."), day)?;
+                println!("created src/days/day{day}.rs");
+            }
+        }
+        Command::Leaderboard { year, id } => {
+            let mut config = ClientConfig::from_env()?;
+            if let Some(year) = year {
+                config.year = year;
+            }
+            let leaderboard = fetch_leaderboard(&config, id, Path::new(CACHE_DIR), &SystemClock)?;
+            println!("{}", leaderboard.render(config.year));
+        }
+        Command::Stats { year } => {
+            let mut config = ClientConfig::from_env()?;
+            if let Some(year) = year {
+                config.year = year;
+            }
+            let calendar = parse_calendar(&fetch_calendar(&config)?);
+            let stats = parse_personal_stats(&fetch_personal_stats(&config)?)?;
+            let registered = if config.year == YEAR {
+                days::DAYS.iter().map(|solver| solver.day).collect_vec()
+            } else {
+                Vec::new()
+            };
+            let statuses = day_statuses(&calendar, &stats, &registered);
+
+            println!(
+                "{:>3}  {:>5}  {:>16}  {:>16}  missing",
+                "day", "stars", "level 1", "level 2"
+            );
+            let mut history = History::load(HISTORY_PATH)?;
+            let mut marked = 0;
+            for status in &statuses {
+                let [level1, level2] = status.levels.clone().map(|level| {
+                    level.map_or("-".to_string(), |level| {
+                        format!("{} #{}", level.time, level.rank)
+                    })
+                });
+                println!(
+                    "{:>3}  {:>5}  {level1:>16}  {level2:>16}  {}",
+                    status.day,
+                    "*".repeat(status.stars as usize),
+                    status.missing().unwrap_or("-")
+                );
+                for level in status.solved_levels() {
+                    if history.mark_solved(config.year, status.day, level)? {
+                        marked += 1;
+                    }
+                }
+            }
+            if marked > 0 {
+                println!("marked {marked} starred level(s) as solved in {HISTORY_PATH}");
+            }
+        }
+        Command::New { day } => {
+            scaffold_day(Path::new("."), day)?;
+            println!("created src/days/day{day}.rs");
+        }
+        Command::Watch { day, once: true } => {
+            let check = check_day(
+                day,
+                &Manifest::load_default()?,
+                Path::new(TEST_INPUT_DIR),
+                &Manifest::load_answers()?,
+                Path::new(INPUT_DIR),
+            );
+            print!("{}", check.render());
+            if !check.is_success() {
+                bail!("day {day} does not pass yet");
+            }
+        }
+        Command::Watch { day, once: false } => {
+            let days_dir = Path::new(DAYS_DIR);
+            let test_input_dir = Path::new(TEST_INPUT_DIR);
+            let mut snapshot = Snapshot::take(&watched_paths(days_dir, test_input_dir, day));
+            loop {
+                rerun_day(day)?;
+                println!("watching day {day}, Ctrl-C to stop");
+                let (next, changed) =
+                    wait_for_change(&SystemClock, days_dir, test_input_dir, day, &snapshot);
+                snapshot = next;
+                println!(
+                    "\n{} changed",
+                    changed.iter().map(|path| path.display()).join(", ")
+                );
+            }
+        }
+        Command::Examples { day, save } => {
+            let puzzle = Puzzle::parse(&fetch_puzzle(&ClientConfig::from_env()?, day)?);
+            for (i, example) in puzzle.examples.iter().enumerate() {
+                println!("--- block {i} ---\n{}", example.trim_end());
+            }
+            for (level, answer) in Level::ALL.iter().zip(&puzzle.answers) {
+                match answer {
+                    Some(ExampleAnswer {
+                        answer,
+                        block: Some(block),
+                    }) => println!("level {level} example answer: {answer} (block {block})"),
+                    _ => println!("level {level} example answer: not found"),
+                }
+            }
+            let mut manifest = Manifest::load_default()?;
+            for (index, name) in save {
+                let example = puzzle
+                    .examples
+                    .get(index)
+                    .ok_or_else(|| anyhow!("there is no block {index}"))?;
+                let path = Path::new(TEST_INPUT_DIR).join(format!("{name}.txt"));
+                if std::fs::read_to_string(&path).is_ok_and(|s| !s.is_empty()) {
+                    bail!("{} already exists", path.display());
+                }
+                std::fs::write(&path, example)?;
+                println!("saved block {index} as {}", path.display());
+                for (&level, example_answer) in Level::ALL.iter().zip(&puzzle.answers) {
+                    let Some(ExampleAnswer { answer, block }) = example_answer else {
+                        continue;
+                    };
+                    if *block == Some(index) {
+                        manifest.add(Expectation {
+                            file: name.clone(),
+                            level,
+                            answer: answer.clone(),
+                        })?;
+                        println!("expecting {answer} for level {level} in the manifest");
+                    }
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Rebuilds and runs `watch --once` in a child, as the running binary still has the old solver.
+/// The child is built with the same profile and features. A failing build or check is shown by
+/// the child and does not stop the watch.
+fn rerun_day(day: u32) -> anyhow::Result<()> {
+    let mut cargo = std::process::Command::new(std::env::var_os("CARGO").unwrap_or("cargo".into()));
+    cargo.args(["run", "--quiet"]);
+    if !cfg!(debug_assertions) {
+        cargo.arg("--release");
+    }
+    if cfg!(feature = "alloc-stats") {
+        cargo.args(["--features", "alloc-stats"]);
+    }
+    cargo.args(["--", "watch", "--once", "--day", &day.to_string()]);
+    let status = cargo.status().context("running cargo")?;
+    if status.success() {
+        println!("day {day}: all passing");
+    }
+    Ok(())
+}
+
+fn alloc_summary(stats: Option<AllocStats>) -> String {
+    match stats {
+        Some(AllocStats {
+            allocations,
+            peak_bytes,
+        }) => format!(
+            ", {allocations} allocations, peak {:.1} KiB",
+            peak_bytes as f64 / 1024.0
+        ),
+        None => String::new(),
+    }
+}
+
+/// Runs the days on their real input in parallel and prints one sorted table, or exports the
+/// results.
+fn run_table(solvers: &[&Solver], levels: &[Level], export: Option<&Export>) -> anyhow::Result<()> {
+    let answers = Manifest::load_answers()?;
+    let workers = std::thread::available_parallelism().map_or(1, |n| n.get());
+    let rows = run_parallel(solvers, levels, Path::new(INPUT_DIR), &answers, workers);
+    if let Some(export) = export {
+        // The solutions and the answers in `input/` are those of YEAR, whatever AOC_YEAR says
+        Report::new(YEAR, &rows, &answers, &History::load(HISTORY_PATH)?).export(export)?;
+    } else {
+        print_table(&rows, &answers);
+    }
+    let failures = rows
+        .iter()
+        .filter(|row| {
+            matches!(
+                row.status,
+                Status::Mismatch(_) | Status::Panicked(_) | Status::Error(_)
+            )
+        })
+        .count();
+    if failures > 0 {
+        bail!("{failures} level(s) failed");
+    }
+    Ok(())
+}
+
+fn print_table(rows: &[Row], answers: &Manifest) {
+    println!(
+        "{:>3}  {:>5}  {:>20}  {:>12}  status",
+        "day", "level", "answer", "time"
+    );
+    for row in rows {
+        let answer = row
+            .answer
+            .as_ref()
+            .map_or("-".to_string(), ToString::to_string);
+        let time = row.time.map_or("-".to_string(), |time| format!("{time:?}"));
+        let status = match &row.status {
+            Status::Mismatch(_) => {
+                let expected = answers.get(&format!("day{}", row.day), row.level);
+                format!("mismatch, expected {}", expected.unwrap())
+            }
+            Status::Unrecorded(_) => "unrecorded".to_string(),
+            status => status.to_string(),
+        };
+        println!(
+            "{:>3}  {:>5}  {answer:>20}  {time:>12}  {status}",
+            row.day, row.level
+        );
+    }
+}
@@ -0,0 +1,156 @@
+//! Allocation counting for the runner. The counting global allocator is only installed with the
+//! `alloc-stats` feature; without it, measurements are `None`.
+
+/// Heap usage of one phase on the current thread.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Calls to `alloc` and `realloc`
+    pub allocations: u64,
+    /// Highest number of live bytes above what was live when the measurement started
+    pub peak_bytes: u64,
+}
+
+/// A measurement in progress, started by [`measure`].
+#[derive(Debug)]
+pub struct Measurement {
+    #[cfg(feature = "alloc-stats")]
+    start: counting::Counters,
+}
+
+pub fn measure() -> Measurement {
+    Measurement {
+        #[cfg(feature = "alloc-stats")]
+        start: counting::start(),
+    }
+}
+
+impl Measurement {
+    pub fn finish(self) -> Option<AllocStats> {
+        #[cfg(feature = "alloc-stats")]
+        {
+            let end = counting::counters();
+            Some(AllocStats {
+                allocations: end.allocations - self.start.allocations,
+                peak_bytes: (end.peak - self.start.current).max(0) as u64,
+            })
+        }
+        #[cfg(not(feature = "alloc-stats"))]
+        None
+    }
+}
+
+#[cfg(feature = "alloc-stats")]
+mod counting {
+    use std::{
+        alloc::{GlobalAlloc, Layout, System},
+        cell::Cell,
+    };
+
+    /// Counted per thread, so days running in parallel do not see each other's allocations.
+    #[derive(Debug, Clone, Copy)]
+    pub struct Counters {
+        pub allocations: u64,
+        /// Can go negative when freeing memory allocated on another thread
+        pub current: i64,
+        pub peak: i64,
+    }
+
+    thread_local! {
+        static COUNTERS: Cell<Counters> = const {
+            Cell::new(Counters {
+                allocations: 0,
+                current: 0,
+                peak: 0,
+            })
+        };
+    }
+
+    fn update(f: impl FnOnce(&mut Counters)) {
+        // Fails during thread teardown, when there is nothing left to measure anyway
+        let _ = COUNTERS.try_with(|cell| {
+            let mut counters = cell.get();
+            f(&mut counters);
+            counters.peak = counters.peak.max(counters.current);
+            cell.set(counters);
+        });
+    }
+
+    pub fn counters() -> Counters {
+        COUNTERS.with(Cell::get)
+    }
+
+    /// Resets the peak to the current usage and returns the counters.
+    pub fn start() -> Counters {
+        update(|counters| counters.peak = counters.current);
+        counters()
+    }
+
+    struct CountingAllocator;
+
+    unsafe impl GlobalAlloc for CountingAllocator {
+        unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc(layout);
+            if !ptr.is_null() {
+                update(|c| {
+                    c.allocations += 1;
+                    c.current += layout.size() as i64;
+                });
+            }
+            ptr
+        }
+
+        unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+            let ptr = System.alloc_zeroed(layout);
+            if !ptr.is_null() {
+                update(|c| {
+                    c.allocations += 1;
+                    c.current += layout.size() as i64;
+                });
+            }
+            ptr
+        }
+
+        unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+            System.dealloc(ptr, layout);
+            update(|c| c.current -= layout.size() as i64);
+        }
+
+        unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+            let new_ptr = System.realloc(ptr, layout, new_size);
+            if !new_ptr.is_null() {
+                update(|c| {
+                    c.allocations += 1;
+                    c.current += new_size as i64 - layout.size() as i64;
+                });
+            }
+            new_ptr
+        }
+    }
+
+    #[global_allocator]
+    static GLOBAL: CountingAllocator = CountingAllocator;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    #[cfg(feature = "alloc-stats")]
+    fn counts_allocations_and_peak() {
+        let measurement = measure();
+        let big = vec![0_u8; 4096];
+        drop(big);
+        let small: Vec<Box<u64>> = (0..10).map(Box::new).collect();
+        let stats = measurement.finish().unwrap();
+        assert_eq!(stats.allocations, 1 + 1 + 10);
+        assert!(stats.peak_bytes >= 4096);
+        drop(small);
+    }
+
+    #[test]
+    #[cfg(not(feature = "alloc-stats"))]
+    fn disabled_without_feature() {
+        assert_eq!(measure().finish(), None);
+    }
+}
@@ -0,0 +1,106 @@
+use std::{convert::Infallible, fmt::Display, str::FromStr};
+
+/// A puzzle answer. Integers are stored in the narrowest variant that holds them, so equal
+/// numbers compare equal no matter which integer type a solution returned.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Answer {
+    Unsigned(u64),
+    Signed(i64),
+    Wide(i128),
+    Text(String),
+}
+
+impl Answer {
+    /// The numeric value, for ordering against too high/too low verdicts.
+    pub fn as_i128(&self) -> Option<i128> {
+        match self {
+            Answer::Unsigned(x) => Some(*x as i128),
+            Answer::Signed(x) => Some(*x as i128),
+            Answer::Wide(x) => Some(*x),
+            Answer::Text(_) => None,
+        }
+    }
+}
+
+impl From<i128> for Answer {
+    fn from(value: i128) -> Self {
+        if let Ok(x) = u64::try_from(value) {
+            Answer::Unsigned(x)
+        } else if let Ok(x) = i64::try_from(value) {
+            Answer::Signed(x)
+        } else {
+            Answer::Wide(value)
+        }
+    }
+}
+
+macro_rules! from_integer {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Self {
+                (value as i128).into()
+            }
+        })*
+    };
+}
+
+from_integer!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl From<String> for Answer {
+    fn from(value: String) -> Self {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Self {
+        Answer::Text(value.to_string())
+    }
+}
+
+impl FromStr for Answer {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        Ok(match s.parse::<i128>() {
+            Ok(x) => x.into(),
+            Err(_) => s.into(),
+        })
+    }
+}
+
+impl Display for Answer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Answer::Unsigned(x) => x.fmt(f),
+            Answer::Signed(x) => x.fmt(f),
+            Answer::Wide(x) => x.fmt(f),
+            Answer::Text(s) => s.fmt(f),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn integers_are_normalised() {
+        assert_eq!(Answer::from(42_u32), Answer::from(42_i64));
+        assert_eq!(Answer::from(-1_i64), Answer::Signed(-1));
+        assert_eq!(Answer::from(i128::MAX), Answer::Wide(i128::MAX));
+    }
+
+    #[test]
+    fn parse_roundtrip() {
+        for answer in [
+            Answer::Unsigned(15690466351717),
+            Answer::Signed(-3),
+            Answer::Wide(-(1 << 100)),
+            Answer::Text("EZFCHJAB".to_string()),
+        ] {
+            assert_eq!(answer.to_string().parse::<Answer>(), Ok(answer));
+        }
+    }
+}
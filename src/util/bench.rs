@@ -0,0 +1,196 @@
+use std::{path::Path, time::Duration};
+
+use anyhow::Context;
+use serde::{Deserialize, Serialize};
+
+use super::{infra::Level, registry::Solver};
+
+/// Summary of the timings of one phase over all iterations, in nanoseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct PhaseStats {
+    pub min_ns: u64,
+    pub median_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl PhaseStats {
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+        let nanos = |d: Duration| d.as_nanos() as u64;
+        let median = match samples.len() {
+            0 => Duration::ZERO,
+            n if n % 2 == 1 => samples[n / 2],
+            n => (samples[n / 2 - 1] + samples[n / 2]) / 2,
+        };
+        let mean = samples.iter().sum::<Duration>() / samples.len().max(1) as u32;
+        Self {
+            min_ns: samples.first().copied().map_or(0, nanos),
+            median_ns: nanos(median),
+            mean_ns: nanos(mean),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct DayBench {
+    pub day: u32,
+    pub parse: PhaseStats,
+    pub level1: PhaseStats,
+    pub level2: PhaseStats,
+}
+
+impl DayBench {
+    pub fn phases(&self) -> [(&'static str, &PhaseStats); 3] {
+        [
+            ("parse", &self.parse),
+            ("level 1", &self.level1),
+            ("level 2", &self.level2),
+        ]
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BenchReport {
+    pub iterations: usize,
+    pub days: Vec<DayBench>,
+}
+
+impl BenchReport {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref();
+        let content =
+            std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+        serde_json::from_str(&content).with_context(|| format!("parsing {}", path.display()))
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> anyhow::Result<()> {
+        let path = path.as_ref();
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)
+            .with_context(|| format!("writing {}", path.display()))
+    }
+}
+
+/// Solves both levels `iterations` times, parsing afresh each time.
+pub fn bench_day(solver: &Solver, input: &str, iterations: usize) -> anyhow::Result<DayBench> {
+    let mut parse = Vec::with_capacity(iterations);
+    let mut level1 = Vec::with_capacity(iterations);
+    let mut level2 = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let run = (solver.run)(input, &Level::ALL)?;
+        parse.push(run.parse_time);
+        for level_run in run.levels {
+            match level_run.level {
+                Level::One => level1.push(level_run.time),
+                Level::Two => level2.push(level_run.time),
+            }
+        }
+    }
+    Ok(DayBench {
+        day: solver.day,
+        parse: PhaseStats::from_samples(&mut parse),
+        level1: PhaseStats::from_samples(&mut level1),
+        level2: PhaseStats::from_samples(&mut level2),
+    })
+}
+
+/// A phase whose median got slower than the baseline by more than the threshold.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Regression {
+    pub day: u32,
+    pub phase: &'static str,
+    pub baseline_ns: u64,
+    pub current_ns: u64,
+}
+
+impl Regression {
+    pub fn percent(&self) -> f64 {
+        (self.current_ns as f64 / self.baseline_ns as f64 - 1.0) * 100.0
+    }
+}
+
+/// Compares medians of the days present in both reports.
+pub fn regressions(
+    baseline: &BenchReport,
+    current: &BenchReport,
+    threshold_percent: f64,
+) -> Vec<Regression> {
+    let mut result = Vec::new();
+    for day in &current.days {
+        let Some(old) = baseline.days.iter().find(|old| old.day == day.day) else {
+            continue;
+        };
+        for ((phase, new_stats), (_, old_stats)) in day.phases().into_iter().zip(old.phases()) {
+            let limit = old_stats.median_ns as f64 * (1.0 + threshold_percent / 100.0);
+            if new_stats.median_ns as f64 > limit {
+                result.push(Regression {
+                    day: day.day,
+                    phase,
+                    baseline_ns: old_stats.median_ns,
+                    current_ns: new_stats.median_ns,
+                });
+            }
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn stats(median_ns: u64) -> PhaseStats {
+        PhaseStats {
+            min_ns: median_ns,
+            median_ns,
+            mean_ns: median_ns,
+        }
+    }
+
+    #[test]
+    fn stats_from_samples() {
+        let mut samples = [5, 1, 3, 100].map(Duration::from_nanos);
+        assert_eq!(
+            PhaseStats::from_samples(&mut samples),
+            PhaseStats {
+                min_ns: 1,
+                median_ns: 4,
+                mean_ns: 27,
+            }
+        );
+        let mut samples = [7, 2, 3].map(Duration::from_nanos);
+        assert_eq!(PhaseStats::from_samples(&mut samples).median_ns, 3);
+    }
+
+    #[test]
+    fn flags_slow_medians() {
+        let report = |parse, level2| BenchReport {
+            iterations: 10,
+            days: vec![DayBench {
+                day: 11,
+                parse: stats(parse),
+                level1: stats(1000),
+                level2: stats(level2),
+            }],
+        };
+        let found = regressions(&report(100, 1000), &report(105, 2000), 10.0);
+        assert_eq!(
+            found,
+            vec![Regression {
+                day: 11,
+                phase: "level 2",
+                baseline_ns: 1000,
+                current_ns: 2000,
+            }]
+        );
+        assert_eq!(found[0].percent(), 100.0);
+        let other_day = BenchReport {
+            iterations: 10,
+            days: Vec::new(),
+        };
+        assert!(regressions(&other_day, &report(100, 1000), 10.0).is_empty());
+    }
+}
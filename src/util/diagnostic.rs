@@ -0,0 +1,177 @@
+use std::fmt::Write;
+
+use anyhow::anyhow;
+use nom_supreme::{
+    error::{BaseErrorKind, ErrorTree, GenericErrorTree, StackContext},
+    final_parser::Location,
+};
+
+use super::prelude::ParseFinalResult;
+
+/// One way the parse failed: where, what was expected there, and the labelled parsers it was in,
+/// innermost first.
+#[derive(Debug)]
+struct Failure {
+    location: Location,
+    expected: String,
+    contexts: Vec<(Location, &'static str)>,
+}
+
+fn collect_failures(
+    tree: &ErrorTree<Location>,
+    outer: &[(Location, &'static str)],
+    acc: &mut Vec<Failure>,
+) {
+    match tree {
+        GenericErrorTree::Base { location, kind } => acc.push(Failure {
+            location: *location,
+            expected: match kind {
+                BaseErrorKind::Expected(expectation) => format!("expected {expectation}"),
+                BaseErrorKind::Kind(kind) => format!("{kind:?} failed"),
+                BaseErrorKind::External(e) => e.to_string(),
+            },
+            contexts: outer.to_vec(),
+        }),
+        GenericErrorTree::Stack { base, contexts } => {
+            // `contexts` is innermost first, so they go in front of the outer ones
+            let mut labels = contexts
+                .iter()
+                .filter_map(|(location, context)| match context {
+                    StackContext::Context(label) => Some((*location, *label)),
+                    StackContext::Kind(_) => None,
+                })
+                .collect::<Vec<_>>();
+            labels.extend_from_slice(outer);
+            collect_failures(base, &labels, acc)
+        }
+        GenericErrorTree::Alt(alternatives) => {
+            for alternative in alternatives {
+                collect_failures(alternative, outer, acc)
+            }
+        }
+    }
+}
+
+/// Renders a parse error against the input it came from: the position that got furthest, what
+/// was expected there, the offending line with a caret, and the `.context(...)` labels around it.
+pub fn render(input: &str, error: &ErrorTree<Location>) -> String {
+    let mut failures = Vec::new();
+    collect_failures(error, &[], &mut failures);
+    let furthest = failures
+        .iter()
+        .map(|failure| (failure.location.line, failure.location.column))
+        .max()
+        .unwrap_or((1, 1));
+    let failures = failures
+        .into_iter()
+        .filter(|failure| (failure.location.line, failure.location.column) == furthest)
+        .collect::<Vec<_>>();
+    let (line_number, column) = furthest;
+
+    let mut expected = failures
+        .iter()
+        .map(|failure| failure.expected.as_str())
+        .collect::<Vec<_>>();
+    expected.dedup();
+    let mut out = format!(
+        "parse error at line {line_number}, column {column}: {}",
+        expected.join(" or ")
+    );
+
+    let line = input.split('\n').nth(line_number - 1).unwrap_or("");
+    let found = line.get(column - 1..).and_then(|rest| rest.chars().next());
+    match found {
+        Some(c) => write!(out, ", found {c:?}").unwrap(),
+        None if line_number > input.split('\n').count() - 1 && !input.ends_with('\n') => {
+            write!(out, ", found end of input").unwrap()
+        }
+        None => write!(out, ", found end of line").unwrap(),
+    }
+    let gutter = line_number.to_string();
+    let shown = line.trim_end_matches('\r');
+    let caret_offset = line
+        .get(..column - 1)
+        .map_or(0, |prefix| prefix.chars().count());
+    write!(
+        out,
+        "\n{gutter} | {shown}\n{} | {}^",
+        " ".repeat(gutter.len()),
+        " ".repeat(caret_offset)
+    )
+    .unwrap();
+
+    if let Some(failure) = failures.first() {
+        for (location, label) in &failure.contexts {
+            write!(out, "\n  in {label} starting at {location:#}").unwrap();
+        }
+    }
+    if found == Some('\r') {
+        out.push_str("\nthe input has Windows line endings (CRLF)");
+    }
+    out
+}
+
+/// Turns the [`ErrorTree`](nom_supreme::error::ErrorTree) of a final parser into a readable error.
+pub trait Diagnose<T> {
+    fn diagnose(self, input: &str) -> anyhow::Result<T>;
+}
+
+impl<T> Diagnose<T> for ParseFinalResult<'_, T> {
+    fn diagnose(self, input: &str) -> anyhow::Result<T> {
+        self.map_err(|error| anyhow!(render(input, &error)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use nom::{
+        character::complete::{newline, u32},
+        combinator::{eof, success},
+        Parser,
+    };
+    use nom_supreme::{final_parser::final_parser, multi::collect_separated_terminated, ParserExt};
+
+    use super::*;
+    use crate::util::prelude::ParseResult;
+
+    fn parse_line(input: &str) -> ParseResult<'_, u32> {
+        u32.terminated(newline).context("number line").parse(input)
+    }
+
+    fn parse_numbers(input: &str) -> ParseFinalResult<'_, Vec<u32>> {
+        final_parser(collect_separated_terminated(parse_line, success(()), eof))(input)
+    }
+
+    #[test]
+    fn points_at_offending_character() {
+        let input = "12\n34x\n";
+        let message = parse_numbers(input)
+            .diagnose(input)
+            .unwrap_err()
+            .to_string();
+        assert_eq!(
+            message,
+            "parse error at line 2, column 3: expected '\\n', found 'x'\n\
+             2 | 34x\n  \
+               |   ^\n  \
+             in number line starting at line 2, column 1"
+        );
+    }
+
+    #[test]
+    fn explains_crlf() {
+        let input = "12\r\n34\r\n";
+        let message = parse_numbers(input)
+            .diagnose(input)
+            .unwrap_err()
+            .to_string();
+        assert!(
+            message.starts_with("parse error at line 1, column 3"),
+            "{message}"
+        );
+        assert!(
+            message.ends_with("Windows line endings (CRLF)"),
+            "{message}"
+        );
+    }
+}
@@ -0,0 +1,258 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, Context};
+
+use super::{
+    answer::Answer,
+    infra::{InputSource, Level, INPUT_DIR, TEST_INPUT_DIR},
+};
+use crate::days;
+
+pub const MANIFEST_FILE: &str = "manifest.tsv";
+/// Accepted answers for the real inputs, in the same format as the example manifest
+pub const ANSWERS_FILE: &str = "answers.tsv";
+
+/// The expected answer of one level on one example input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Expectation {
+    /// Name of the file in `test_input`, without extension
+    pub file: String,
+    pub level: Level,
+    pub answer: Answer,
+}
+
+impl Expectation {
+    /// Example files are named `dayN` or `dayN_suffix`.
+    pub fn day(&self) -> Option<u32> {
+        let rest = self.file.strip_prefix("day")?;
+        let end = rest.find('_').unwrap_or(rest.len());
+        rest[..end].parse().ok()
+    }
+}
+
+/// Tab separated `file level answer` lines, with `#` comments. The default manifest is empty
+/// and has no file to [`add`](Self::add) to.
+#[derive(Debug, Default)]
+pub struct Manifest {
+    path: PathBuf,
+    pub expectations: Vec<Expectation>,
+}
+
+impl Manifest {
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let content = match std::fs::read_to_string(&path) {
+            Ok(content) => content,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        };
+        let expectations = content
+            .lines()
+            .enumerate()
+            .filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+            .map(|(i, line)| {
+                parse_expectation(line)
+                    .with_context(|| format!("{}:{}: bad manifest line", path.display(), i + 1))
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { path, expectations })
+    }
+
+    /// The manifest next to the example inputs.
+    pub fn load_default() -> anyhow::Result<Self> {
+        Self::load(Path::new(TEST_INPUT_DIR).join(MANIFEST_FILE))
+    }
+
+    /// The accepted answers next to the real inputs.
+    pub fn load_answers() -> anyhow::Result<Self> {
+        Self::load(Path::new(INPUT_DIR).join(ANSWERS_FILE))
+    }
+
+    pub fn get(&self, file: &str, level: Level) -> Option<&Answer> {
+        self.expectations
+            .iter()
+            .find(|e| e.file == file && e.level == level)
+            .map(|e| &e.answer)
+    }
+
+    /// Appends an expectation unless the manifest already has one for that file and level.
+    pub fn add(&mut self, expectation: Expectation) -> anyhow::Result<bool> {
+        if self.get(&expectation.file, expectation.level).is_some() {
+            return Ok(false);
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening {}", self.path.display()))?;
+        writeln!(
+            file,
+            "{}\t{}\t{}",
+            expectation.file, expectation.level, expectation.answer
+        )
+        .with_context(|| format!("writing {}", self.path.display()))?;
+        self.expectations.push(expectation);
+        Ok(true)
+    }
+}
+
+fn parse_expectation(line: &str) -> anyhow::Result<Expectation> {
+    let mut fields = line.splitn(3, '\t');
+    let mut next = |name| fields.next().ok_or_else(|| anyhow!("missing {name}"));
+    let file = next("file")?.to_string();
+    let level = next("level")?.parse()?;
+    let answer = next("answer")?.parse().unwrap();
+    Ok(Expectation {
+        file,
+        level,
+        answer,
+    })
+}
+
+/// How the current code fares against an [`Expectation`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Status {
+    Pass,
+    Mismatch(Answer),
+    Panicked(String),
+    /// The input file does not exist, e.g. a real input that was never downloaded
+    MissingInput,
+    /// There is no expected answer to compare this one to
+    Unrecorded(Answer),
+    Error(String),
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Status::Pass => write!(f, "ok"),
+            Status::Mismatch(answer) => write!(f, "got {answer}"),
+            Status::Panicked(message) => write!(f, "panicked: {message}"),
+            Status::MissingInput => write!(f, "missing input"),
+            Status::Unrecorded(answer) => write!(f, "unrecorded, got {answer}"),
+            Status::Error(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+/// Runs every expectation through the registered days on the inputs in `dir`, catching panics so
+/// a single run reports all broken days.
+pub fn check<'a>(manifest: &'a Manifest, dir: &Path) -> Vec<(&'a Expectation, Status)> {
+    manifest
+        .expectations
+        .iter()
+        .map(|expectation| (expectation, check_one(expectation, dir)))
+        .collect()
+}
+
+/// Runs a single expectation, see [`check`].
+pub fn check_one(expectation: &Expectation, dir: &Path) -> Status {
+    let Some(day) = expectation.day() else {
+        return Status::Error("file name does not start with a day".to_string());
+    };
+    let Some(solver) = days::get(day) else {
+        return Status::Error(format!("day {day} is not registered"));
+    };
+    let path = dir.join(format!("{}.txt", expectation.file));
+    if !path.exists() {
+        return Status::MissingInput;
+    }
+    let input = match InputSource::Path(path).load(day) {
+        Ok(input) => input,
+        Err(e) => return Status::Error(format!("{e:#}")),
+    };
+    match catch_unwind(AssertUnwindSafe(|| {
+        (solver.run)(&input, &[expectation.level])
+    })) {
+        Ok(Err(e)) => Status::Error(format!("{e:#}")),
+        Ok(Ok(mut run)) => {
+            let answer = run.levels.remove(0).answer;
+            if answer == expectation.answer {
+                Status::Pass
+            } else {
+                Status::Mismatch(answer)
+            }
+        }
+        Err(payload) => Status::Panicked(panic_message(&*payload)),
+    }
+}
+
+/// Describes every example in the manifest that does not produce its expected answer.
+pub fn check_examples(manifest: &Manifest) -> Vec<String> {
+    check(manifest, Path::new(TEST_INPUT_DIR))
+        .into_iter()
+        .filter(|(_, status)| *status != Status::Pass)
+        .map(|(expectation, status)| {
+            let problem = match status {
+                Status::Mismatch(answer) => {
+                    format!("expected {}, got {answer}", expectation.answer)
+                }
+                status => status.to_string(),
+            };
+            format!(
+                "{} level {}: {problem}",
+                expectation.file, expectation.level
+            )
+        })
+        .collect()
+}
+
+pub fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        s.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn day_from_file_name() {
+        let expectation = |file: &str| Expectation {
+            file: file.to_string(),
+            level: Level::One,
+            answer: 0_u32.into(),
+        };
+        assert_eq!(expectation("day8").day(), Some(8));
+        assert_eq!(expectation("day10_loop_complex").day(), Some(10));
+        assert_eq!(expectation("notes").day(), None);
+    }
+
+    #[test]
+    fn failures_are_collected() {
+        let manifest = Manifest {
+            path: PathBuf::new(),
+            expectations: vec![
+                Expectation {
+                    file: "day7".to_string(),
+                    level: Level::One,
+                    answer: 6440_u32.into(),
+                },
+                Expectation {
+                    file: "day7".to_string(),
+                    level: Level::Two,
+                    answer: 1_u32.into(),
+                },
+                Expectation {
+                    file: "day24".to_string(),
+                    level: Level::One,
+                    answer: 1_u32.into(),
+                },
+            ],
+        };
+        let failures = check_examples(&manifest);
+        assert_eq!(failures.len(), 2, "{failures:?}");
+        assert_eq!(failures[0], "day7 level 2: expected 1, got 5905");
+        assert_eq!(failures[1], "day24 level 1: day 24 is not registered");
+    }
+}
@@ -0,0 +1,244 @@
+use std::{io::Write, path::PathBuf, str::FromStr};
+
+use anyhow::{bail, Context};
+use serde::Serialize;
+
+use super::{
+    examples::{Manifest, Status},
+    infra::{History, Level},
+    runner::Row,
+};
+
+/// Bumped whenever a field is renamed, removed or changes meaning; new fields may be added
+/// without a bump.
+pub const SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            _ => bail!("expected json or csv, got {s}"),
+        }
+    }
+}
+
+/// Where `run --format` writes its results; stdout unless `--output` names a file.
+#[derive(Debug, Clone)]
+pub struct Export {
+    pub format: Format,
+    pub output: Option<PathBuf>,
+}
+
+/// One level of one day, as exported.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Record {
+    pub day: u32,
+    pub level: u8,
+    pub answer: Option<String>,
+    /// Recorded accepted answer, if any
+    pub expected: Option<String>,
+    pub time_ns: Option<u64>,
+    /// One of `ok`, `mismatch`, `unrecorded`, `panicked`, `missing_input` and `error`
+    pub status: &'static str,
+    /// Panic or error message
+    pub message: Option<String>,
+    /// The site's verdict on the last answer submitted from here, e.g. `correct` or `too_high`
+    pub verdict: Option<&'static str>,
+    /// Whether the submission history shows the level as solved, i.e. its star was earned
+    pub star: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub schema_version: u32,
+    pub year: i32,
+    pub results: Vec<Record>,
+}
+
+impl Report {
+    /// Combines the rows of a run with the recorded answers and the submission `history` of
+    /// `year`.
+    pub fn new(year: i32, rows: &[Row], answers: &Manifest, history: &History) -> Self {
+        let results = rows
+            .iter()
+            .map(|row| {
+                let expected = answers.get(&format!("day{}", row.day), row.level);
+                let (status, message) = match &row.status {
+                    Status::Pass => ("ok", None),
+                    Status::Mismatch(_) => ("mismatch", None),
+                    Status::Unrecorded(_) => ("unrecorded", None),
+                    Status::Panicked(message) => ("panicked", Some(message.clone())),
+                    Status::MissingInput => ("missing_input", None),
+                    Status::Error(message) => ("error", Some(message.clone())),
+                };
+                Record {
+                    day: row.day,
+                    level: match row.level {
+                        Level::One => 1,
+                        Level::Two => 2,
+                    },
+                    answer: row.answer.as_ref().map(ToString::to_string),
+                    expected: expected.map(ToString::to_string),
+                    time_ns: row.time.map(|time| time.as_nanos() as u64),
+                    status,
+                    message,
+                    verdict: history
+                        .latest_verdict(year, row.day, row.level)
+                        .map(|verdict| verdict.name()),
+                    star: history.is_solved(year, row.day, row.level),
+                }
+            })
+            .collect();
+        Self {
+            schema_version: SCHEMA_VERSION,
+            year,
+            results,
+        }
+    }
+
+    pub fn write(&self, format: Format, mut out: impl Write) -> anyhow::Result<()> {
+        match format {
+            Format::Json => {
+                serde_json::to_writer_pretty(&mut out, self)?;
+                writeln!(out)?;
+            }
+            Format::Csv => {
+                writeln!(
+                    out,
+                    "day,level,answer,expected,time_ns,status,message,verdict,star"
+                )?;
+                for record in &self.results {
+                    let optional =
+                        |value: &Option<String>| value.as_deref().map_or(String::new(), csv_field);
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{},{},{},{}",
+                        record.day,
+                        record.level,
+                        optional(&record.answer),
+                        optional(&record.expected),
+                        record.time_ns.map_or(String::new(), |ns| ns.to_string()),
+                        record.status,
+                        optional(&record.message),
+                        record.verdict.unwrap_or_default(),
+                        record.star
+                    )?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn export(&self, export: &Export) -> anyhow::Result<()> {
+        match &export.output {
+            Some(path) => {
+                let file = std::fs::File::create(path)
+                    .with_context(|| format!("creating {}", path.display()))?;
+                self.write(export.format, std::io::BufWriter::new(file))
+                    .with_context(|| format!("writing {}", path.display()))
+            }
+            None => self.write(export.format, std::io::stdout().lock()),
+        }
+    }
+}
+
+/// Quotes a field if it contains a separator, quote or line break.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::util::{examples::Expectation, scratch::TempDir};
+
+    fn report(name: &str) -> Report {
+        let mut answers = Manifest::default();
+        for (level, answer) in [(Level::One, 6440_u32), (Level::Two, 9999)] {
+            answers.expectations.push(Expectation {
+                file: "day7".to_string(),
+                level,
+                answer: answer.into(),
+            });
+        }
+        // Level 2 has a recorded answer that was never accepted
+        let dir = TempDir::new(&format!("export-{name}"));
+        let path = dir.join("history.tsv");
+        std::fs::write(
+            &path,
+            "2023\t7\t1\ttoo_low\t6000\n2023\t7\t1\tcorrect\t6440\n2023\t7\t2\ttoo_high\t9999\n",
+        )
+        .unwrap();
+        let history = History::load(&path).unwrap();
+        let rows = [
+            Row {
+                day: 7,
+                level: Level::One,
+                answer: Some(6440_u32.into()),
+                time: Some(Duration::from_micros(12)),
+                status: Status::Pass,
+            },
+            Row {
+                day: 7,
+                level: Level::Two,
+                answer: None,
+                time: None,
+                status: Status::Panicked("bad, \"card\"".to_string()),
+            },
+        ];
+        Report::new(2023, &rows, &answers, &history)
+    }
+
+    #[test]
+    fn json_schema() {
+        let mut out = Vec::new();
+        report("json").write(Format::Json, &mut out).unwrap();
+        let value: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "schema_version": 1,
+                "year": 2023,
+                "results": [
+                    {
+                        "day": 7, "level": 1, "answer": "6440", "expected": "6440",
+                        "time_ns": 12000, "status": "ok", "message": null,
+                        "verdict": "correct", "star": true
+                    },
+                    {
+                        "day": 7, "level": 2, "answer": null, "expected": "9999",
+                        "time_ns": null, "status": "panicked", "message": "bad, \"card\"",
+                        "verdict": "too_high", "star": false
+                    }
+                ]
+            })
+        );
+    }
+
+    #[test]
+    fn csv_quotes_fields() {
+        let mut out = Vec::new();
+        report("csv").write(Format::Csv, &mut out).unwrap();
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "day,level,answer,expected,time_ns,status,message,verdict,star\n\
+             7,1,6440,6440,12000,ok,,correct,true\n\
+             7,2,,9999,,panicked,\"bad, \"\"card\"\"\",too_high,false\n"
+        );
+    }
+}
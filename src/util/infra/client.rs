@@ -0,0 +1,446 @@
+use std::{fmt::Display, path::Path, time::SystemTime};
+
+use anyhow::{bail, Context};
+
+use super::{
+    clock::{countdown, Clock},
+    config::ClientConfig,
+    history::{Blocked, Entry, History},
+    html, input_path, schedule, Level, Verdict, INPUT_DIR,
+};
+use crate::util::answer::Answer;
+
+const USER_AGENT: &str = "github.com/bnarnold/adventofcode-2023";
+
+/// Downloads the puzzle input, or returns the copy cached in `input/` from an earlier call. See
+/// [`input_path`] for where each year is cached.
+pub fn fetch_input(config: &ClientConfig, day: u32) -> anyhow::Result<String> {
+    fetch_input_to(config, day, Path::new(INPUT_DIR))
+}
+
+fn fetch_input_to(config: &ClientConfig, day: u32, dir: &Path) -> anyhow::Result<String> {
+    let year = config.year;
+    let path = input_path(dir, year, day);
+    if path.exists() {
+        return std::fs::read_to_string(&path)
+            .with_context(|| format!("reading cached {}", path.display()));
+    }
+    if !(1..=25).contains(&day) {
+        bail!("There is no puzzle for day {day}");
+    }
+    if !schedule::is_unlocked(year, day, SystemTime::now()) {
+        bail!("The puzzle for day {day} of {year} is not unlocked yet");
+    }
+
+    let input = get(config, &format!("/day/{day}/input"), "input")?;
+    if looks_like_html(&input) {
+        bail!("Got an HTML page instead of the input, the session is probably invalid");
+    }
+
+    std::fs::create_dir_all(path.parent().unwrap_or(dir)).context("creating input directory")?;
+    std::fs::write(&path, &input).with_context(|| format!("writing {}", path.display()))?;
+    Ok(input)
+}
+
+/// Adds the headers every request to the site carries.
+fn with_session(request: ureq::Request, config: &ClientConfig) -> ureq::Request {
+    request
+        .set("User-Agent", USER_AGENT)
+        .set("Cookie", &config.session.cookie())
+}
+
+/// Downloads a page of the configured year, `what` naming it in errors. Unless it was already
+/// accepted, the session is checked first, so an expired cookie fails before anything is
+/// downloaded.
+fn get(config: &ClientConfig, path: &str, what: &str) -> anyhow::Result<String> {
+    whoami(config)?;
+    match with_session(ureq::get(&config.url(path)), config).call() {
+        Ok(response) => response
+            .into_string()
+            .with_context(|| format!("reading {what}")),
+        Err(ureq::Error::Status(status, response)) => {
+            let body = response.into_string().unwrap_or_default();
+            bail!(
+                "Fetching {what} failed with status {status}: {}",
+                body.trim()
+            )
+        }
+        Err(e) => Err(e).with_context(|| format!("Fetching {what} failed")),
+    }
+}
+
+/// Downloads the puzzle description page, which includes the second level once the first one
+/// is solved.
+pub fn fetch_puzzle(config: &ClientConfig, day: u32) -> anyhow::Result<String> {
+    if !schedule::is_unlocked(config.year, day, SystemTime::now()) {
+        bail!(
+            "The puzzle for day {day} of {} is not unlocked yet",
+            config.year
+        );
+    }
+    get(config, &format!("/day/{day}"), "puzzle")
+}
+
+/// Downloads the JSON of a private leaderboard. The site asks to do this at most every 15
+/// minutes, see [`fetch_leaderboard`](super::leaderboard::fetch_leaderboard) for the cache.
+pub fn fetch_leaderboard_json(config: &ClientConfig, id: u64) -> anyhow::Result<String> {
+    let body = get(
+        config,
+        &format!("/leaderboard/private/view/{id}.json"),
+        &format!("leaderboard {id}"),
+    )?;
+    if looks_like_html(&body) {
+        bail!("Got an HTML page instead of leaderboard {id}, the session is probably invalid or not a member");
+    }
+    Ok(body)
+}
+
+/// Loads the event page without following redirects, as expired sessions are redirected to the
+/// login page. Returns the user name the page shows and the page itself.
+fn event_page(config: &ClientConfig) -> anyhow::Result<(String, String)> {
+    let agent = ureq::AgentBuilder::new().redirects(0).build();
+    let page = match with_session(agent.get(&config.url("")), config).call() {
+        Ok(response) if response.status() == 200 => {
+            response.into_string().context("reading event page")?
+        }
+        Ok(response) => bail!(
+            "The session is invalid or expired, the site redirected to {}",
+            response.header("Location").unwrap_or("another page")
+        ),
+        Err(ureq::Error::Status(status, _)) => {
+            bail!("The session is invalid or expired, the site answered with status {status}")
+        }
+        Err(e) => return Err(e).context("Checking the session failed"),
+    };
+    let name =
+        user_name(&page).context("The session is invalid or expired, the site shows no user")?;
+    config.session.accepted_as(&name);
+    Ok((name, page))
+}
+
+/// Checks that the session is valid, returning the user name the site shows. Only the first
+/// check of a session asks the site.
+pub fn whoami(config: &ClientConfig) -> anyhow::Result<String> {
+    if let Some(name) = config.session.user() {
+        return Ok(name.to_string());
+    }
+    event_page(config).map(|(name, _)| name)
+}
+
+/// Logged in pages show the user in `<div class="user">`, followed by the star count.
+fn user_name(html: &str) -> Option<String> {
+    let regex = regex::Regex::new(r#"<div class="user">([^<]*)"#).unwrap();
+    let name = regex.captures(html)?.get(1)?.as_str().trim();
+    (!name.is_empty()).then(|| html::unescape(name))
+}
+
+/// Downloads the event calendar, which shows the stars earned per day. This is the page the
+/// session is checked on, so it only needs one request.
+pub fn fetch_calendar(config: &ClientConfig) -> anyhow::Result<String> {
+    event_page(config).map(|(_, page)| page)
+}
+
+/// Downloads the personal stats page with rank and time per solved level.
+pub fn fetch_personal_stats(config: &ClientConfig) -> anyhow::Result<String> {
+    get(config, "/leaderboard/self", "personal stats")
+}
+
+/// Inputs are plain text, so markup means we were served a login or error page.
+fn looks_like_html(body: &str) -> bool {
+    let start = body.trim_start();
+    start
+        .get(..9)
+        .is_some_and(|s| s.eq_ignore_ascii_case("<!doctype"))
+        || start.starts_with("<html")
+}
+
+/// What happened to an answer handed to [`submit`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Outcome {
+    Sent(Verdict),
+    /// The answer history already tells us the result, so nothing was sent
+    Blocked(Blocked),
+}
+
+impl Outcome {
+    pub fn is_success(&self, answer: &Answer) -> bool {
+        match self {
+            Outcome::Sent(verdict) => verdict.is_success(),
+            Outcome::Blocked(blocked) => blocked.is_success(answer),
+        }
+    }
+}
+
+impl Display for Outcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Sent(verdict) => verdict.fmt(f),
+            Outcome::Blocked(blocked) => write!(f, "not submitted, {blocked}"),
+        }
+    }
+}
+
+/// How often [`submit`] sends an answer again after a cooldown before giving up.
+const MAX_RETRIES: usize = 3;
+
+/// Submits an answer unless `history` already knows how it would be judged, and records the
+/// verdict. If the site asks us to wait and `retry` is set, the cooldown is counted down on
+/// `clock` and the answer is sent again, at most [`MAX_RETRIES`] times.
+pub fn submit(
+    config: &ClientConfig,
+    day: u32,
+    level: Level,
+    answer: &Answer,
+    history: &mut History,
+    clock: &impl Clock,
+    retry: bool,
+) -> anyhow::Result<Outcome> {
+    if let Some(blocked) = history.check(config.year, day, level, answer) {
+        return Ok(Outcome::Blocked(blocked));
+    }
+    whoami(config)?;
+    let mut retries = 0;
+    loop {
+        let verdict = post_answer(config, day, level, answer)?;
+        if let Verdict::RateLimited { wait } = verdict {
+            if !retry || retries == MAX_RETRIES {
+                return Ok(Outcome::Sent(verdict));
+            }
+            retries += 1;
+            countdown(clock, wait, "cooldown");
+            continue;
+        }
+        history.record(Entry {
+            year: config.year,
+            day,
+            level,
+            answer: Some(answer.clone()),
+            verdict,
+        })?;
+        return Ok(Outcome::Sent(verdict));
+    }
+}
+
+fn post_answer(
+    config: &ClientConfig,
+    day: u32,
+    level: Level,
+    answer: &Answer,
+) -> anyhow::Result<Verdict> {
+    let body = with_session(
+        ureq::post(&config.url(&format!("/day/{day}/answer"))),
+        config,
+    )
+    .send_form(&[
+        ("level", &level.to_string()),
+        ("answer", &answer.to_string()),
+    ])
+    .context("Submit failed")?
+    .into_string()
+    .context("reading submit response")?;
+    Verdict::parse(&body)
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use super::*;
+    use crate::util::{
+        infra::{clock::test::FakeClock, mock::MockServer},
+        scratch::TempDir,
+    };
+
+    fn temp_history(name: &str) -> (TempDir, History) {
+        let dir = TempDir::new(name);
+        let history = History::load(dir.join("history.tsv")).unwrap();
+        (dir, history)
+    }
+
+    #[test]
+    fn waits_out_cooldown_and_retries() {
+        let server = MockServer::start(2023).with_answer(7, Level::One, 6440_u32);
+        server.rate_limit(Duration::from_secs(43));
+        let (_dir, mut history) = temp_history("cooldown-retry");
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH);
+        let answer = Answer::from(6440_u32);
+        let outcome = submit(
+            &server.config(),
+            7,
+            Level::One,
+            &answer,
+            &mut history,
+            &clock,
+            true,
+        )
+        .unwrap();
+        assert_eq!(outcome, Outcome::Sent(Verdict::Correct));
+        assert_eq!(clock.slept.get(), Duration::from_secs(43));
+        assert_eq!(
+            server.requests(),
+            [
+                "GET /2023",
+                "POST /2023/day/7/answer",
+                "POST /2023/day/7/answer"
+            ]
+        );
+        assert_eq!(history.entries().len(), 1);
+    }
+
+    #[test]
+    fn reports_cooldown_without_retry() {
+        let server = MockServer::start(2023).with_answer(7, Level::One, 6440_u32);
+        server.rate_limit(Duration::from_secs(303));
+        let (_dir, mut history) = temp_history("cooldown-report");
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH);
+        let outcome = submit(
+            &server.config(),
+            7,
+            Level::One,
+            &Answer::from(6440_u32),
+            &mut history,
+            &clock,
+            false,
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            Outcome::Sent(Verdict::RateLimited {
+                wait: Duration::from_secs(303)
+            })
+        );
+        assert_eq!(clock.slept.get(), Duration::ZERO);
+        assert_eq!(server.requests().len(), 2);
+        assert!(history.entries().is_empty());
+    }
+
+    #[test]
+    fn gives_up_after_repeated_cooldowns() {
+        let server = MockServer::start(2023).with_answer(7, Level::One, 6440_u32);
+        for _ in 0..5 {
+            server.rate_limit(Duration::from_secs(10));
+        }
+        let (_dir, mut history) = temp_history("cooldown-give-up");
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH);
+        let outcome = submit(
+            &server.config(),
+            7,
+            Level::One,
+            &Answer::from(6440_u32),
+            &mut history,
+            &clock,
+            true,
+        )
+        .unwrap();
+        assert_eq!(
+            outcome,
+            Outcome::Sent(Verdict::RateLimited {
+                wait: Duration::from_secs(10)
+            })
+        );
+        assert_eq!(clock.slept.get(), Duration::from_secs(30));
+        assert_eq!(server.requests().len(), 1 + 1 + MAX_RETRIES);
+        assert!(history.entries().is_empty());
+    }
+
+    #[test]
+    fn verdicts_from_the_site() {
+        let server = MockServer::start(2023).with_answer(8, Level::One, 1000_u32);
+        let config = server.config();
+        let post = |answer: u32| post_answer(&config, 8, Level::One, &answer.into()).unwrap();
+        assert_eq!(post(2000), Verdict::TooHigh);
+        assert_eq!(post(10), Verdict::TooLow);
+        assert_eq!(post(1000), Verdict::Correct);
+        assert_eq!(post(1000), Verdict::WrongLevel);
+        assert_eq!(
+            post_answer(&config, 8, Level::Two, &"abc".into()).unwrap(),
+            Verdict::Incorrect
+        );
+    }
+
+    #[test]
+    fn locked_level_is_not_solved() {
+        let server = MockServer::start(2023).with_answer(7, Level::Two, 5905_u32);
+        let (_dir, mut history) = temp_history("wrong-level");
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH);
+        let answer = Answer::from(5905_u32);
+        let outcome = submit(
+            &server.config(),
+            7,
+            Level::Two,
+            &answer,
+            &mut history,
+            &clock,
+            false,
+        )
+        .unwrap();
+        assert_eq!(outcome, Outcome::Sent(Verdict::WrongLevel));
+        assert!(!outcome.is_success(&answer));
+        assert!(history.entries().is_empty());
+        assert_eq!(history.check(2023, 7, Level::Two, &answer), None);
+    }
+
+    #[test]
+    fn input_is_cached_and_needs_a_session() {
+        let server = MockServer::start(2023).with_input(3, "467..114..\n");
+        let dir = TempDir::new("fetch");
+
+        let error = fetch_input_to(&server.config_with_session("expired"), 3, &dir).unwrap_err();
+        assert!(error.to_string().contains("invalid or expired"), "{error}");
+        assert!(!dir.join("day3.txt").exists());
+
+        assert_eq!(
+            fetch_input_to(&server.config(), 3, &dir).unwrap(),
+            "467..114..\n"
+        );
+        assert_eq!(
+            fetch_input_to(&server.config(), 3, &dir).unwrap(),
+            "467..114..\n"
+        );
+        assert_eq!(
+            server.requests(),
+            ["GET /2023", "GET /2023", "GET /2023/day/3/input"],
+            "the second fetch is served from the cache"
+        );
+    }
+
+    #[test]
+    fn other_years_do_not_share_the_cache() {
+        let server = MockServer::start(2022).with_input(1, "2022\n");
+        let dir = TempDir::new("fetch-years");
+        std::fs::write(dir.join("day1.txt"), "2023\n").unwrap();
+
+        assert_eq!(fetch_input_to(&server.config(), 1, &dir).unwrap(), "2022\n");
+        assert_eq!(
+            std::fs::read_to_string(dir.join("2022").join("day1.txt")).unwrap(),
+            "2022\n"
+        );
+        assert_eq!(
+            std::fs::read_to_string(dir.join("day1.txt")).unwrap(),
+            "2023\n"
+        );
+    }
+
+    #[test]
+    fn session_check() {
+        let server = MockServer::start(2023);
+        assert_eq!(whoami(&server.config()).unwrap(), "mock user");
+        let error = whoami(&server.config_with_session("secret-token")).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "The session is invalid or expired, the site redirected to /2023/auth/login"
+        );
+        assert_eq!(
+            user_name(include_str!("./test_input/calendar_2023.html")).as_deref(),
+            Some("Ada")
+        );
+    }
+
+    #[test]
+    fn html_is_not_input() {
+        assert!(looks_like_html("<!DOCTYPE html>\n<html lang=\"en-us\">"));
+        assert!(looks_like_html("<html><body>log in</body></html>"));
+        assert!(!looks_like_html("467..114..\n...*......\n"));
+        assert!(!looks_like_html(""));
+    }
+}
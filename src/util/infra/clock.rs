@@ -0,0 +1,78 @@
+use std::{
+    io::Write,
+    time::{Duration, SystemTime},
+};
+
+/// Source of time for anything that waits, so tests can run without sleeping.
+pub trait Clock {
+    fn now(&self) -> SystemTime;
+    fn sleep(&self, duration: Duration);
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep(&self, duration: Duration) {
+        std::thread::sleep(duration)
+    }
+}
+
+/// Sleeps for `duration`, showing the remaining seconds on stderr.
+pub fn countdown(clock: &impl Clock, duration: Duration, label: &str) {
+    let end = clock.now() + duration;
+    while let Ok(remaining) = end.duration_since(clock.now()) {
+        if remaining.is_zero() {
+            break;
+        }
+        eprint!("\r{label}: {}s left ", remaining.as_secs_f64().ceil());
+        let _ = std::io::stderr().flush();
+        clock.sleep(remaining.min(Duration::from_secs(1)));
+    }
+    eprintln!("\r{label}: done     ");
+}
+
+#[cfg(test)]
+pub mod test {
+    use std::cell::Cell;
+
+    use super::*;
+
+    /// A clock that only moves when slept on.
+    #[derive(Debug)]
+    pub struct FakeClock {
+        now: Cell<SystemTime>,
+        pub slept: Cell<Duration>,
+    }
+
+    impl FakeClock {
+        pub fn at(now: SystemTime) -> Self {
+            Self {
+                now: Cell::new(now),
+                slept: Cell::new(Duration::ZERO),
+            }
+        }
+    }
+
+    impl Clock for FakeClock {
+        fn now(&self) -> SystemTime {
+            self.now.get()
+        }
+
+        fn sleep(&self, duration: Duration) {
+            self.now.set(self.now.get() + duration);
+            self.slept.set(self.slept.get() + duration);
+        }
+    }
+
+    #[test]
+    fn countdown_sleeps_exactly() {
+        let clock = FakeClock::at(SystemTime::UNIX_EPOCH);
+        countdown(&clock, Duration::from_millis(2500), "test");
+        assert_eq!(clock.slept.get(), Duration::from_millis(2500));
+    }
+}
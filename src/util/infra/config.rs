@@ -0,0 +1,214 @@
+use std::{
+    fmt::Debug,
+    path::{Path, PathBuf},
+    sync::{Arc, OnceLock},
+};
+
+use anyhow::{bail, Context};
+
+use super::YEAR;
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The session cookie, which is as good as a password. It is only ever written into the
+/// `Cookie` header, never formatted for logs or errors.
+#[derive(Clone)]
+pub struct Session {
+    token: String,
+    /// The user the site showed for this cookie, shared between clones so that the session is
+    /// checked at most once per process
+    user: Arc<OnceLock<String>>,
+}
+
+impl Session {
+    pub fn new(token: impl AsRef<str>) -> Self {
+        Self {
+            token: token.as_ref().trim().to_string(),
+            user: Arc::default(),
+        }
+    }
+
+    /// Value of the `Cookie` header.
+    pub fn cookie(&self) -> String {
+        format!("session={}", self.token)
+    }
+
+    /// The user name, if the site already accepted this session.
+    pub fn user(&self) -> Option<&str> {
+        self.user.get().map(String::as_str)
+    }
+
+    /// Remembers that the site accepted this session as `name`.
+    pub fn accepted_as(&self, name: &str) {
+        let _ = self.user.set(name.to_string());
+    }
+}
+
+impl PartialEq for Session {
+    fn eq(&self, other: &Self) -> bool {
+        self.token == other.token
+    }
+}
+
+impl Eq for Session {}
+
+impl Debug for Session {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Session(<redacted>)")
+    }
+}
+
+/// Where and as whom to talk to the Advent of Code site.
+#[derive(Debug, Clone)]
+pub struct ClientConfig {
+    /// Site root without trailing slash, overridable to point at a local server
+    pub base_url: String,
+    pub year: i32,
+    pub session: Session,
+}
+
+/// `$XDG_CONFIG_HOME/aoc/session`, falling back to `~/.config/aoc/session`.
+pub fn session_file() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| Path::new(&home).join(".config")))?;
+    Some(config_dir.join("aoc").join("session"))
+}
+
+/// Reads the session from a file that nobody but its owner may read.
+fn read_session_file(path: &Path) -> anyhow::Result<Option<Session>> {
+    let metadata = match std::fs::metadata(path) {
+        Ok(metadata) => metadata,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+    };
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mode = metadata.permissions().mode();
+        if mode & 0o077 != 0 {
+            bail!(
+                "{} is readable by others (mode {:o}), run chmod 600 on it",
+                path.display(),
+                mode & 0o777
+            );
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = metadata;
+    let token =
+        std::fs::read_to_string(path).with_context(|| format!("reading {}", path.display()))?;
+    Ok(Some(Session::new(token)))
+}
+
+fn year_from_lookup(lookup: impl Fn(&str) -> Option<String>) -> anyhow::Result<Option<i32>> {
+    lookup("AOC_YEAR")
+        .map(|year| year.parse().context("AOC_YEAR must be a year"))
+        .transpose()
+}
+
+impl ClientConfig {
+    /// Reads `AOC_BASE_URL`, `AOC_YEAR` and `SESSION` from the environment, then from `.env` if
+    /// there is one. The session may also come from [`session_file`].
+    pub fn from_env() -> anyhow::Result<Self> {
+        let _ = dotenvy::dotenv();
+        Self::from_lookup(|name| std::env::var(name).ok(), session_file().as_deref())
+    }
+
+    /// `AOC_YEAR` from the environment or `.env`, if it is set. Unlike
+    /// [`from_env`](Self::from_env) this needs no session.
+    pub fn year_from_env() -> anyhow::Result<Option<i32>> {
+        let _ = dotenvy::dotenv();
+        year_from_lookup(|name| std::env::var(name).ok())
+    }
+
+    fn from_lookup(
+        lookup: impl Fn(&str) -> Option<String>,
+        session_file: Option<&Path>,
+    ) -> anyhow::Result<Self> {
+        let base_url = lookup("AOC_BASE_URL")
+            .map(|url| url.trim_end_matches('/').to_string())
+            .unwrap_or_else(|| DEFAULT_BASE_URL.to_string());
+        let year = year_from_lookup(&lookup)?.unwrap_or(YEAR);
+        let token = lookup("SESSION").filter(|token| !token.trim().is_empty());
+        let session = match (token, session_file) {
+            (Some(token), _) => Session::new(token),
+            (None, Some(path)) => read_session_file(path)?.with_context(|| {
+                format!(
+                    "SESSION must be set in the environment, in .env or in {} to talk to the site",
+                    path.display()
+                )
+            })?,
+            (None, None) => bail!("SESSION must be set to talk to the site"),
+        };
+        Ok(Self {
+            base_url,
+            year,
+            session,
+        })
+    }
+
+    /// URL of a page for this year, `path` starting with a slash.
+    pub fn url(&self, path: &str) -> String {
+        format!("{}/{}{path}", self.base_url, self.year)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::collections::HashMap;
+
+    use super::*;
+    use crate::util::scratch::TempDir;
+
+    #[test]
+    fn defaults_and_overrides() {
+        let vars = HashMap::from([("SESSION", "abc")]);
+        let config = ClientConfig::from_lookup(|name| vars.get(name).map(|s| s.to_string()), None);
+        let config = config.unwrap();
+        assert_eq!(
+            config.url("/day/1/input"),
+            "https://adventofcode.com/2023/day/1/input"
+        );
+
+        let vars = HashMap::from([
+            ("SESSION", "abc"),
+            ("AOC_YEAR", "2022"),
+            ("AOC_BASE_URL", "http://127.0.0.1:8080/"),
+        ]);
+        let config = ClientConfig::from_lookup(|name| vars.get(name).map(|s| s.to_string()), None);
+        assert_eq!(
+            config.unwrap().url("/day/1/answer"),
+            "http://127.0.0.1:8080/2022/day/1/answer"
+        );
+
+        assert!(ClientConfig::from_lookup(|_| None, None).is_err());
+    }
+
+    #[test]
+    fn session_file_is_the_fallback() {
+        let dir = TempDir::new("session");
+        let path = dir.join("session");
+        std::fs::write(&path, "from-file\n").unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            let error = ClientConfig::from_lookup(|_| None, Some(&path)).unwrap_err();
+            assert!(error.to_string().contains("chmod 600"), "{error}");
+            assert!(!error.to_string().contains("from-file"));
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o600)).unwrap();
+        }
+        let config = ClientConfig::from_lookup(|_| None, Some(&path)).unwrap();
+        assert_eq!(config.session, Session::new("from-file"));
+
+        let from_env = |name: &str| (name == "SESSION").then(|| "from-env".to_string());
+        let config = ClientConfig::from_lookup(from_env, Some(&path)).unwrap();
+        assert_eq!(config.session.cookie(), "session=from-env");
+        assert!(!format!("{config:?}").contains("from-env"));
+        std::fs::remove_file(&path).unwrap();
+
+        let error = ClientConfig::from_lookup(|_| None, Some(&path)).unwrap_err();
+        assert!(error.to_string().contains("aoc-session"), "{error}");
+    }
+}
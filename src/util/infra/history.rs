@@ -0,0 +1,336 @@
+use std::{
+    fmt::Display,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use anyhow::{anyhow, bail, Context};
+
+use super::{Level, Verdict};
+use crate::util::answer::Answer;
+
+pub const HISTORY_PATH: &str = "input/history.tsv";
+
+/// One submitted answer and the site's verdict on it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Entry {
+    pub year: i32,
+    pub day: u32,
+    pub level: Level,
+    pub answer: Option<Answer>,
+    pub verdict: Verdict,
+}
+
+/// Why a submission was not sent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Blocked {
+    /// The level is already solved, with this answer if we know it
+    Solved { answer: Option<Answer> },
+    /// This exact answer was rejected before
+    Rejected(Verdict),
+    /// An answer at most this large was already too high
+    AboveTooHigh(Answer),
+    /// An answer at least this large was already too low
+    BelowTooLow(Answer),
+}
+
+impl Blocked {
+    /// Whether `answer` is known to be the right one.
+    pub fn is_success(&self, answer: &Answer) -> bool {
+        match self {
+            Blocked::Solved { answer: solution } => solution.as_ref().is_none_or(|s| s == answer),
+            _ => false,
+        }
+    }
+}
+
+impl Display for Blocked {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Blocked::Solved {
+                answer: Some(answer),
+            } => {
+                write!(f, "level already solved with answer {answer}")
+            }
+            Blocked::Solved { answer: None } => write!(f, "level already solved"),
+            Blocked::Rejected(verdict) => write!(f, "answer was already rejected as {verdict}"),
+            Blocked::AboveTooHigh(bound) => write!(f, "{bound} was already too high"),
+            Blocked::BelowTooLow(bound) => write!(f, "{bound} was already too low"),
+        }
+    }
+}
+
+/// Submissions recorded on disk as tab separated lines, one per verdict.
+#[derive(Debug)]
+pub struct History {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+impl History {
+    /// Reads the history at `path`, which is empty if the file does not exist yet.
+    pub fn load(path: impl AsRef<Path>) -> anyhow::Result<Self> {
+        let path = path.as_ref().to_path_buf();
+        let entries = match std::fs::read_to_string(&path) {
+            Ok(content) => content
+                .lines()
+                .enumerate()
+                .filter(|(_, line)| !line.is_empty())
+                .map(|(i, line)| {
+                    parse_entry(line)
+                        .with_context(|| format!("{}:{}: bad history entry", path.display(), i + 1))
+                })
+                .collect::<anyhow::Result<_>>()?,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Vec::new(),
+            Err(e) => return Err(e).with_context(|| format!("reading {}", path.display())),
+        };
+        Ok(Self { path, entries })
+    }
+
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Appends an entry to the file. Rate limits and wrong levels say nothing about the answer
+    /// and are skipped.
+    pub fn record(&mut self, entry: Entry) -> anyhow::Result<()> {
+        if matches!(
+            entry.verdict,
+            Verdict::RateLimited { .. } | Verdict::WrongLevel
+        ) {
+            return Ok(());
+        }
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("creating {}", parent.display()))?;
+        }
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .with_context(|| format!("opening {}", self.path.display()))?;
+        writeln!(file, "{}", format_entry(&entry))
+            .with_context(|| format!("writing {}", self.path.display()))?;
+        self.entries.push(entry);
+        Ok(())
+    }
+
+    pub fn is_solved(&self, year: i32, day: u32, level: Level) -> bool {
+        self.entries.iter().any(|e| {
+            (e.year, e.day, e.level) == (year, day, level)
+                && matches!(e.verdict, Verdict::Correct | Verdict::AlreadySolved)
+        })
+    }
+
+    /// The verdict on the last answer recorded for a level.
+    pub fn latest_verdict(&self, year: i32, day: u32, level: Level) -> Option<Verdict> {
+        self.entries
+            .iter()
+            .rfind(|e| (e.year, e.day, e.level) == (year, day, level))
+            .map(|e| e.verdict)
+    }
+
+    /// The answer the site accepted for a level, if it was submitted from here.
+    pub fn accepted_answer(&self, year: i32, day: u32, level: Level) -> Option<&Answer> {
+        self.entries
+            .iter()
+            .filter(|e| (e.year, e.day, e.level) == (year, day, level))
+            .find(|e| e.verdict == Verdict::Correct)
+            .and_then(|e| e.answer.as_ref())
+    }
+
+    /// Records a level as solved without knowing the answer, e.g. because the site shows its
+    /// star, so that it is never submitted. Returns whether it was not marked before.
+    pub fn mark_solved(&mut self, year: i32, day: u32, level: Level) -> anyhow::Result<bool> {
+        if self.is_solved(year, day, level) {
+            return Ok(false);
+        }
+        self.record(Entry {
+            year,
+            day,
+            level,
+            answer: None,
+            verdict: Verdict::AlreadySolved,
+        })?;
+        Ok(true)
+    }
+
+    /// Checks whether submitting `answer` could tell us anything new.
+    pub fn check(&self, year: i32, day: u32, level: Level, answer: &Answer) -> Option<Blocked> {
+        let mut entries = self
+            .entries
+            .iter()
+            .filter(|e| e.year == year && e.day == day && e.level == level);
+        if entries
+            .clone()
+            .any(|e| matches!(e.verdict, Verdict::Correct | Verdict::AlreadySolved))
+        {
+            let answer = self.accepted_answer(year, day, level).cloned();
+            return Some(Blocked::Solved { answer });
+        }
+        if let Some(rejected) = entries.clone().find(|e| e.answer.as_ref() == Some(answer)) {
+            return Some(Blocked::Rejected(rejected.verdict));
+        }
+        entries.find_map(|entry| {
+            let bound = entry.answer.as_ref()?;
+            let ordering = compare(answer, bound)?;
+            match entry.verdict {
+                Verdict::TooHigh if ordering.is_ge() => Some(Blocked::AboveTooHigh(bound.clone())),
+                Verdict::TooLow if ordering.is_le() => Some(Blocked::BelowTooLow(bound.clone())),
+                _ => None,
+            }
+        })
+    }
+}
+
+fn compare(a: &Answer, b: &Answer) -> Option<std::cmp::Ordering> {
+    Some(a.as_i128()?.cmp(&b.as_i128()?))
+}
+
+fn format_entry(entry: &Entry) -> String {
+    let answer = entry
+        .answer
+        .as_ref()
+        .map(|a| a.to_string())
+        .unwrap_or_default();
+    // Rate limits and wrong levels are never recorded
+    let verdict = entry.verdict.name();
+    format!(
+        "{}\t{}\t{}\t{verdict}\t{answer}",
+        entry.year, entry.day, entry.level
+    )
+}
+
+fn parse_entry(line: &str) -> anyhow::Result<Entry> {
+    let mut fields = line.splitn(5, '\t');
+    let mut next = |name| fields.next().ok_or_else(|| anyhow!("missing {name}"));
+    let year = next("year")?.parse().context("year")?;
+    let day = next("day")?.parse().context("day")?;
+    let level = next("level")?.parse()?;
+    let verdict = match next("verdict")? {
+        "correct" => Verdict::Correct,
+        "incorrect" => Verdict::Incorrect,
+        "too_high" => Verdict::TooHigh,
+        "too_low" => Verdict::TooLow,
+        "already_solved" => Verdict::AlreadySolved,
+        other => bail!("unknown verdict {other}"),
+    };
+    let answer = next("answer")?;
+    let answer = (!answer.is_empty()).then(|| answer.parse().unwrap());
+    Ok(Entry {
+        year,
+        day,
+        level,
+        answer,
+        verdict,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::scratch::TempDir;
+
+    fn history(entries: &[(u64, Verdict)]) -> History {
+        History {
+            path: PathBuf::new(),
+            entries: entries
+                .iter()
+                .map(|&(answer, verdict)| Entry {
+                    year: 2023,
+                    day: 7,
+                    level: Level::One,
+                    answer: Some(answer.into()),
+                    verdict,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn blocks_known_bad_answers() {
+        let history = history(&[
+            (100, Verdict::TooLow),
+            (500, Verdict::TooHigh),
+            (300, Verdict::Incorrect),
+        ]);
+        let check = |answer: u64| history.check(2023, 7, Level::One, &answer.into());
+        assert_eq!(check(300), Some(Blocked::Rejected(Verdict::Incorrect)));
+        assert_eq!(check(500), Some(Blocked::Rejected(Verdict::TooHigh)));
+        assert_eq!(check(600), Some(Blocked::AboveTooHigh(500_u64.into())));
+        assert_eq!(check(50), Some(Blocked::BelowTooLow(100_u64.into())));
+        assert_eq!(check(200), None);
+        assert_eq!(history.check(2023, 7, Level::Two, &600_u64.into()), None);
+        assert_eq!(history.check(2022, 7, Level::One, &600_u64.into()), None);
+    }
+
+    #[test]
+    fn solved_level_is_never_resubmitted() {
+        let history = history(&[(100, Verdict::TooLow), (250, Verdict::Correct)]);
+        let blocked = history.check(2023, 7, Level::One, &200_u64.into()).unwrap();
+        assert_eq!(
+            blocked,
+            Blocked::Solved {
+                answer: Some(250_u64.into())
+            }
+        );
+        assert!(!blocked.is_success(&200_u64.into()));
+        assert!(blocked.is_success(&250_u64.into()));
+        assert_eq!(
+            history.accepted_answer(2023, 7, Level::One),
+            Some(&250_u64.into())
+        );
+        assert_eq!(history.accepted_answer(2023, 7, Level::Two), None);
+    }
+
+    #[test]
+    fn starred_level_is_marked_once() {
+        let dir = TempDir::new("marked");
+        let path = dir.join("history.tsv");
+        let mut history = History::load(&path).unwrap();
+        assert!(history.mark_solved(2023, 3, Level::Two).unwrap());
+        assert!(!history.mark_solved(2023, 3, Level::Two).unwrap());
+        assert_eq!(
+            History::load(&path)
+                .unwrap()
+                .check(2023, 3, Level::Two, &1_u64.into()),
+            Some(Blocked::Solved { answer: None })
+        );
+    }
+
+    #[test]
+    fn roundtrip_through_file() {
+        let dir = TempDir::new("history");
+        let path = dir.join("history.tsv");
+        let mut history = History::load(&path).unwrap();
+        assert!(history.entries().is_empty());
+        let entries = [
+            Entry {
+                year: 2023,
+                day: 8,
+                level: Level::Two,
+                answer: Some(15690466351717_u64.into()),
+                verdict: Verdict::Correct,
+            },
+            Entry {
+                year: 2023,
+                day: 9,
+                level: Level::One,
+                answer: None,
+                verdict: Verdict::AlreadySolved,
+            },
+        ];
+        for entry in entries.clone() {
+            history.record(entry).unwrap();
+        }
+        history
+            .record(Entry {
+                verdict: Verdict::RateLimited {
+                    wait: std::time::Duration::from_secs(1),
+                },
+                ..entries[0].clone()
+            })
+            .unwrap();
+        assert_eq!(History::load(&path).unwrap().entries(), entries);
+    }
+}
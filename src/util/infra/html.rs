@@ -0,0 +1,39 @@
+use regex::Regex;
+
+/// Removes all tags, keeping their text content.
+pub fn strip_tags(html: &str) -> String {
+    Regex::new(r"<[^>]*>").unwrap().replace_all(html, "").into()
+}
+
+/// Replaces the character entities the site uses in text.
+pub fn unescape(text: &str) -> String {
+    text.replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&#39;", "'")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&")
+}
+
+/// The inner HTML of every `<tag ...>...</tag>`, in document order. Does not handle nesting.
+pub fn elements<'a>(html: &'a str, tag: &str) -> Vec<&'a str> {
+    let regex = Regex::new(&format!(r"(?s)<{tag}(?:\s[^>]*)?>(.*?)</{tag}>")).unwrap();
+    regex
+        .captures_iter(html)
+        .map(|captures| captures.get(1).unwrap().as_str())
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn text_of_elements() {
+        let html = r#"<article class="day-desc"><p>a &lt;b&gt; <em>c</em></p></article><article>d</article>"#;
+        let articles = elements(html, "article");
+        assert_eq!(articles.len(), 2);
+        assert_eq!(unescape(&strip_tags(articles[0])), "a <b> c");
+        assert_eq!(articles[1], "d");
+    }
+}
@@ -0,0 +1,198 @@
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Write,
+    path::Path,
+    time::{Duration, SystemTime},
+};
+
+use anyhow::Context;
+use serde::Deserialize;
+
+use super::{client::fetch_leaderboard_json, clock::Clock, config::ClientConfig, unlock_time};
+
+/// The site asks not to poll a leaderboard more often than this.
+pub const POLL_INTERVAL: Duration = Duration::from_secs(15 * 60);
+/// Leaderboards name other people, so they are cached outside of the tracked `input/`.
+pub const CACHE_DIR: &str = "target/aoc-cache";
+
+/// A private leaderboard as served by `/{year}/leaderboard/private/view/{id}.json`.
+#[derive(Debug, Deserialize)]
+pub struct Leaderboard {
+    pub event: String,
+    pub owner_id: u64,
+    pub members: HashMap<String, Member>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Member {
+    pub id: u64,
+    /// `None` for anonymous users
+    pub name: Option<String>,
+    pub stars: u32,
+    pub local_score: u64,
+    /// Stars earned per day and level
+    pub completion_day_level: BTreeMap<u32, BTreeMap<u8, Star>>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct Star {
+    /// Unix timestamp of when the star was earned
+    pub get_star_ts: u64,
+}
+
+impl Member {
+    pub fn display_name(&self) -> String {
+        self.name
+            .clone()
+            .unwrap_or_else(|| format!("(anonymous user #{})", self.id))
+    }
+}
+
+impl Leaderboard {
+    pub fn parse(json: &str) -> anyhow::Result<Self> {
+        serde_json::from_str(json).context("parsing leaderboard JSON")
+    }
+
+    /// Members by descending local score, then stars.
+    pub fn ranked(&self) -> Vec<&Member> {
+        let mut members = self.members.values().collect::<Vec<_>>();
+        members.sort_by(|a, b| {
+            (b.local_score, b.stars)
+                .cmp(&(a.local_score, a.stars))
+                .then_with(|| a.display_name().cmp(&b.display_name()))
+        });
+        members
+    }
+
+    /// A ranked table with one column per day that anyone solved, showing the stars earned that
+    /// day and how long after the unlock the last of them came.
+    pub fn render(&self, year: i32) -> String {
+        let days = self
+            .members
+            .values()
+            .flat_map(|member| member.completion_day_level.keys().copied())
+            .collect::<BTreeSet<_>>();
+        let mut out = format!(
+            "{:>4}  {:>5}  {:>5}  {:<24}",
+            "rank", "score", "stars", "name"
+        );
+        for day in &days {
+            write!(out, "  {:>10}", format!("day {day}")).unwrap();
+        }
+        for (rank, member) in self.ranked().into_iter().enumerate() {
+            write!(
+                out,
+                "\n{:>4}  {:>5}  {:>5}  {:<24}",
+                rank + 1,
+                member.local_score,
+                member.stars,
+                member.display_name()
+            )
+            .unwrap();
+            for day in &days {
+                let cell = match member.completion_day_level.get(day) {
+                    Some(levels) => {
+                        let last = levels.values().map(|star| star.get_star_ts).max().unwrap();
+                        let unlock = unlock_time(year, *day)
+                            .duration_since(SystemTime::UNIX_EPOCH)
+                            .unwrap()
+                            .as_secs();
+                        let stars = "*".repeat(levels.len());
+                        format!(
+                            "{stars:<2} {}",
+                            format_duration(last.saturating_sub(unlock))
+                        )
+                    }
+                    None => "-".to_string(),
+                };
+                write!(out, "  {cell:>10}").unwrap();
+            }
+        }
+        out
+    }
+}
+
+fn format_duration(secs: u64) -> String {
+    if secs >= 24 * 3600 {
+        ">24h".to_string()
+    } else {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    }
+}
+
+/// Whether a copy cached at `modified` may still be used at `now`.
+fn is_fresh(modified: SystemTime, now: SystemTime) -> bool {
+    now.duration_since(modified)
+        .is_ok_and(|age| age < POLL_INTERVAL)
+}
+
+/// Returns the leaderboard, downloading it only if the copy cached in `cache_dir` is older than
+/// [`POLL_INTERVAL`].
+pub fn fetch_leaderboard(
+    config: &ClientConfig,
+    id: u64,
+    cache_dir: &Path,
+    clock: &impl Clock,
+) -> anyhow::Result<Leaderboard> {
+    let path = cache_dir.join(format!("leaderboard_{}_{id}.json", config.year));
+    let cached = std::fs::metadata(&path)
+        .and_then(|metadata| metadata.modified())
+        .is_ok_and(|modified| is_fresh(modified, clock.now()));
+    if cached {
+        let json = std::fs::read_to_string(&path)
+            .with_context(|| format!("reading cached {}", path.display()))?;
+        return Leaderboard::parse(&json);
+    }
+    let json = fetch_leaderboard_json(config, id)?;
+    let leaderboard = Leaderboard::parse(&json)?;
+    std::fs::create_dir_all(cache_dir)
+        .with_context(|| format!("creating {}", cache_dir.display()))?;
+    std::fs::write(&path, &json).with_context(|| format!("writing {}", path.display()))?;
+    Ok(leaderboard)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::{
+        infra::{clock::test::FakeClock, config::Session},
+        scratch::TempDir,
+    };
+
+    const FIXTURE: &str = include_str!("test_input/leaderboard.json");
+
+    #[test]
+    fn ranked_table() {
+        let leaderboard = Leaderboard::parse(FIXTURE).unwrap();
+        assert_eq!(leaderboard.event, "2023");
+        assert_eq!(leaderboard.members.len(), 3);
+        assert_eq!(
+            leaderboard.render(2023),
+            "rank  score  stars  name                           day 1       day 2\n   \
+                1     12      4  Grace                     ** 0:05:00  ** 0:08:20\n   \
+                2     10      3  Ada                       ** 0:20:00  *  1:00:00\n   \
+                3      1      1  (anonymous user #202)        *  >24h           -"
+        );
+    }
+
+    #[test]
+    fn cache_honours_poll_interval() {
+        let dir = TempDir::new("leaderboard");
+        let path = dir.join("leaderboard_2023_101.json");
+        std::fs::write(&path, FIXTURE).unwrap();
+        let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+        // Nothing listens here, so any request fails
+        let config = ClientConfig {
+            base_url: "http://127.0.0.1:9".to_string(),
+            year: 2023,
+            session: Session::new("abc"),
+        };
+
+        let clock = FakeClock::at(modified + Duration::from_secs(14 * 60));
+        let leaderboard = fetch_leaderboard(&config, 101, &dir, &clock);
+        assert_eq!(leaderboard.unwrap().owner_id, 101);
+
+        let clock = FakeClock::at(modified + POLL_INTERVAL);
+        assert!(fetch_leaderboard(&config, 101, &dir, &clock).is_err());
+    }
+}
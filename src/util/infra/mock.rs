@@ -0,0 +1,426 @@
+//! An offline stand-in for the Advent of Code site, so the client layer can be tested under
+//! `cargo test`.
+
+use std::{
+    collections::{HashMap, HashSet, VecDeque},
+    io::{BufRead, BufReader, Read, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc, Mutex,
+    },
+    thread::JoinHandle,
+    time::Duration,
+};
+
+use super::{
+    config::{ClientConfig, Session},
+    Level,
+};
+use crate::util::answer::Answer;
+
+/// The only session the server accepts.
+pub const SESSION: &str = "mock-session";
+
+#[derive(Debug, Default)]
+struct State {
+    inputs: HashMap<u32, String>,
+    /// Any other page by path, e.g. puzzle descriptions, the calendar or the personal stats
+    pages: HashMap<String, String>,
+    answers: HashMap<(u32, Level), Answer>,
+    solved: HashSet<(u32, Level)>,
+    /// Cooldowns handed out to the next answer submissions instead of a verdict
+    cooldowns: VecDeque<Duration>,
+    leaderboards: HashMap<u64, String>,
+    /// `METHOD path` of every request, in order
+    requests: Vec<String>,
+}
+
+struct Response {
+    status: &'static str,
+    headers: Vec<(&'static str, String)>,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: impl Into<String>) -> Self {
+        Self {
+            status: "200 OK",
+            headers: Vec::new(),
+            body: body.into(),
+        }
+    }
+
+    fn not_found() -> Self {
+        Self {
+            status: "404 Not Found",
+            headers: Vec::new(),
+            body: "404 Not Found".to_string(),
+        }
+    }
+
+    fn redirect(location: String) -> Self {
+        Self {
+            status: "302 Found",
+            headers: vec![("Location", location)],
+            body: String::new(),
+        }
+    }
+}
+
+/// Serves one year of the site on an ephemeral port until dropped.
+pub struct MockServer {
+    pub year: i32,
+    address: SocketAddr,
+    state: Arc<Mutex<State>>,
+    stop: Arc<AtomicBool>,
+    handle: Option<JoinHandle<()>>,
+}
+
+impl MockServer {
+    pub fn start(year: i32) -> Self {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let address = listener.local_addr().unwrap();
+        let state = Arc::new(Mutex::new(State::default()));
+        let stop = Arc::new(AtomicBool::new(false));
+        let handle = std::thread::spawn({
+            let state = state.clone();
+            let stop = stop.clone();
+            move || {
+                for stream in listener.incoming() {
+                    if stop.load(Ordering::SeqCst) {
+                        break;
+                    }
+                    if let Ok(stream) = stream {
+                        handle_connection(stream, year, &state);
+                    }
+                }
+            }
+        });
+        Self {
+            year,
+            address,
+            state,
+            stop,
+            handle: Some(handle),
+        }
+    }
+
+    pub fn base_url(&self) -> String {
+        format!("http://{}", self.address)
+    }
+
+    /// A configuration logged in with the accepted session.
+    pub fn config(&self) -> ClientConfig {
+        self.config_with_session(SESSION)
+    }
+
+    pub fn config_with_session(&self, session: &str) -> ClientConfig {
+        ClientConfig {
+            base_url: self.base_url(),
+            year: self.year,
+            session: Session::new(session),
+        }
+    }
+
+    pub fn with_input(self, day: u32, input: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .inputs
+            .insert(day, input.to_string());
+        self
+    }
+
+    /// Serves `html` at `path`, which is relative to the year, e.g. `/day/1` or `` for the
+    /// calendar.
+    pub fn with_page(self, path: &str, html: &str) -> Self {
+        let path = format!("/{}{path}", self.year);
+        self.state
+            .lock()
+            .unwrap()
+            .pages
+            .insert(path, html.to_string());
+        self
+    }
+
+    pub fn with_answer(self, day: u32, level: Level, answer: impl Into<Answer>) -> Self {
+        let answer = answer.into();
+        self.state
+            .lock()
+            .unwrap()
+            .answers
+            .insert((day, level), answer);
+        self
+    }
+
+    pub fn with_leaderboard(self, id: u64, json: &str) -> Self {
+        self.state
+            .lock()
+            .unwrap()
+            .leaderboards
+            .insert(id, json.to_string());
+        self
+    }
+
+    /// Makes the next answer submission hit a cooldown of `wait`.
+    pub fn rate_limit(&self, wait: Duration) {
+        self.state.lock().unwrap().cooldowns.push_back(wait);
+    }
+
+    pub fn requests(&self) -> Vec<String> {
+        self.state.lock().unwrap().requests.clone()
+    }
+}
+
+impl Drop for MockServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::SeqCst);
+        // Wake up the accept loop so it sees the flag
+        let _ = TcpStream::connect(self.address);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn handle_connection(stream: TcpStream, year: i32, state: &Mutex<State>) {
+    let mut reader = BufReader::new(stream);
+    let mut request_line = String::new();
+    if reader.read_line(&mut request_line).is_err() || request_line.is_empty() {
+        return;
+    }
+    let mut content_length = 0;
+    let mut cookie = String::new();
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header).is_err() || header.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().unwrap_or(0);
+            } else if name.eq_ignore_ascii_case("cookie") {
+                cookie = value.trim().to_string();
+            }
+        }
+    }
+    let mut body = vec![0; content_length];
+    if reader.read_exact(&mut body).is_err() {
+        return;
+    }
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return;
+    };
+    let logged_in = cookie
+        .split(';')
+        .any(|c| c.trim() == format!("session={SESSION}"));
+    let response = {
+        let mut state = state.lock().unwrap();
+        state.requests.push(format!("{method} {path}"));
+        route(
+            &mut state,
+            year,
+            method,
+            path,
+            &String::from_utf8_lossy(&body),
+            logged_in,
+        )
+    };
+    let mut head = format!(
+        "HTTP/1.1 {}\r\nContent-Type: text/html\r\nContent-Length: {}\r\nConnection: close\r\n",
+        response.status,
+        response.body.len()
+    );
+    for (name, value) in response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    let _ = write!(reader.get_mut(), "{head}\r\n{}", response.body);
+}
+
+fn route(
+    state: &mut State,
+    year: i32,
+    method: &str,
+    path: &str,
+    body: &str,
+    logged_in: bool,
+) -> Response {
+    let login = format!("/{year}/auth/login");
+    if path == login {
+        return Response::ok(
+            "<!DOCTYPE html>\n<html><body><p>Log in with GitHub</p></body></html>",
+        );
+    }
+    let Some(rest) = path.strip_prefix(&format!("/{year}")) else {
+        return Response::not_found();
+    };
+    let segments = rest.split('/').skip(1).collect::<Vec<_>>();
+    if !logged_in {
+        // The site refuses inputs outright and sends everything else to the login page
+        return if segments.last() == Some(&"input") {
+            Response {
+                status: "400 Bad Request",
+                headers: Vec::new(),
+                body: "Puzzle inputs differ by user.  Please log in to get your puzzle input.\n"
+                    .to_string(),
+            }
+        } else {
+            Response::redirect(login)
+        };
+    }
+    match (method, &segments[..]) {
+        ("GET", ["day", day, "input"]) => day
+            .parse()
+            .ok()
+            .and_then(|day: u32| state.inputs.get(&day))
+            .map_or_else(Response::not_found, |input| Response::ok(input.clone())),
+        ("POST", ["day", day, "answer"]) => match day.parse() {
+            Ok(day) => Response::ok(answer_page(state, year, day, body)),
+            Err(_) => Response::not_found(),
+        },
+        ("GET", ["leaderboard", "private", "view", file]) => file
+            .strip_suffix(".json")
+            .and_then(|id| id.parse().ok())
+            .and_then(|id: u64| state.leaderboards.get(&id))
+            .map_or_else(
+                || Response::redirect(format!("/{year}/leaderboard/private")),
+                |json| Response::ok(json.clone()),
+            ),
+        ("GET", []) if !state.pages.contains_key(path) => Response::ok(
+            "<!DOCTYPE html>\n<html><body><header><div class=\"user\">mock user <span class=\"star-count\">0*</span></div></header></body></html>",
+        ),
+        ("GET", _) => state
+            .pages
+            .get(path)
+            .map_or_else(Response::not_found, |page| Response::ok(page.clone())),
+        _ => Response::not_found(),
+    }
+}
+
+/// Judges a submitted `level=..&answer=..` form like the site does.
+fn answer_page(state: &mut State, year: i32, day: u32, form: &str) -> String {
+    let field = |name: &str| {
+        form.split('&')
+            .find_map(|pair| pair.strip_prefix(name)?.strip_prefix('='))
+            .unwrap_or_default()
+    };
+    let text = if let Some(wait) = state.cooldowns.pop_front() {
+        let (minutes, seconds) = (wait.as_secs() / 60, wait.as_secs() % 60);
+        let left = if minutes > 0 {
+            format!("{minutes}m {seconds}s")
+        } else {
+            format!("{seconds}s")
+        };
+        format!("You gave an answer too recently; you have to wait after submitting an answer before trying again.  You have {left} left to wait.")
+    } else {
+        match (
+            field("level").parse::<Level>(),
+            field("answer").parse::<Answer>(),
+        ) {
+            (Ok(level), Ok(answer))
+                if !state.solved.contains(&(day, level))
+                    && (level == Level::One || state.solved.contains(&(day, Level::One))) =>
+            {
+                match state.answers.get(&(day, level)) {
+                    Some(expected) if *expected == answer => {
+                        state.solved.insert((day, level));
+                        "That's the right answer!  You are one gold star closer to saving the world.".to_string()
+                    }
+                    expected => {
+                        let hint =
+                            match expected.and_then(|e| Some((e.as_i128()?, answer.as_i128()?))) {
+                                Some((expected, answer)) if answer > expected => {
+                                    "; your answer is too high"
+                                }
+                                Some((expected, answer)) if answer < expected => {
+                                    "; your answer is too low"
+                                }
+                                _ => "",
+                            };
+                        format!("That's not the right answer{hint}.  If you're stuck, make sure you're using the full input data.")
+                    }
+                }
+            }
+            _ => "You don't seem to be solving the right level.  Did you already complete it?"
+                .to_string(),
+        }
+    };
+    format!(
+        "<!DOCTYPE html>\n<html><body><main><article><p>{text} <a href=\"/{year}/day/{day}\">[Return to Day {day}]</a></p></article></main></body></html>"
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::{
+        infra::{
+            clock::test::FakeClock, fetch_calendar, fetch_leaderboard, fetch_personal_stats,
+            fetch_puzzle, parse_calendar, parse_personal_stats, Puzzle,
+        },
+        scratch::TempDir,
+    };
+
+    #[test]
+    fn pages_need_a_session() {
+        let server = MockServer::start(2023)
+            .with_page("/day/1", include_str!("test_input/puzzle_day1.html"))
+            .with_page("", include_str!("test_input/calendar_2023.html"))
+            .with_page(
+                "/leaderboard/self",
+                include_str!("test_input/stats_2023.html"),
+            );
+        let config = server.config();
+
+        let puzzle = Puzzle::parse(&fetch_puzzle(&config, 1).unwrap());
+        assert!(!puzzle.examples.is_empty());
+        assert_eq!(parse_calendar(&fetch_calendar(&config).unwrap())[&1], 2);
+        let stats = parse_personal_stats(&fetch_personal_stats(&config).unwrap()).unwrap();
+        assert_eq!(stats.len(), 3);
+
+        // The session was checked once; a bad one fails the check before anything is downloaded
+        let expired = server.config_with_session("expired");
+        let error = fetch_personal_stats(&expired).unwrap_err();
+        assert!(error.to_string().contains("invalid or expired"), "{error}");
+        assert!(fetch_calendar(&expired).is_err());
+        assert_eq!(
+            server.requests(),
+            [
+                "GET /2023",
+                "GET /2023/day/1",
+                "GET /2023",
+                "GET /2023/leaderboard/self",
+                "GET /2023",
+                "GET /2023"
+            ]
+        );
+    }
+
+    #[test]
+    fn leaderboard_through_the_cache() {
+        let server = MockServer::start(2023)
+            .with_leaderboard(101, include_str!("test_input/leaderboard.json"));
+        let dir = TempDir::new("mock-board");
+        // Ahead of the cache file written below, however coarse the file times are
+        let clock = FakeClock::at(std::time::SystemTime::now() + Duration::from_secs(60));
+
+        let error = fetch_leaderboard(&server.config_with_session("expired"), 101, &dir, &clock);
+        assert!(error
+            .unwrap_err()
+            .to_string()
+            .contains("session is invalid or expired"));
+        assert!(fetch_leaderboard(&server.config(), 7, &dir, &clock).is_err());
+
+        let leaderboard = fetch_leaderboard(&server.config(), 101, &dir, &clock).unwrap();
+        assert_eq!(leaderboard.members.len(), 3);
+        fetch_leaderboard(&server.config(), 101, &dir, &clock).unwrap();
+        let fetched = server
+            .requests()
+            .iter()
+            .filter(|request| *request == "GET /2023/leaderboard/private/view/101.json")
+            .count();
+        assert_eq!(fetched, 1, "the expired session never gets to download it");
+    }
+}
@@ -0,0 +1,342 @@
+use std::{
+    fmt::Display,
+    io::Read,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use anyhow::{anyhow, bail, Context};
+
+use crate::util::export::Export;
+
+mod client;
+mod clock;
+mod config;
+mod history;
+mod html;
+mod leaderboard;
+#[cfg(test)]
+pub mod mock;
+mod puzzle;
+mod scaffold;
+mod schedule;
+mod stats;
+mod verdict;
+
+pub use client::{
+    fetch_calendar, fetch_input, fetch_personal_stats, fetch_puzzle, submit, whoami, Outcome,
+};
+#[cfg(test)]
+pub use clock::test::FakeClock;
+pub use clock::{countdown, Clock, SystemClock};
+pub use config::{session_file, ClientConfig, Session, DEFAULT_BASE_URL};
+pub use history::{Blocked, Entry, History, HISTORY_PATH};
+pub use leaderboard::{fetch_leaderboard, Leaderboard, Member, CACHE_DIR, POLL_INTERVAL};
+pub use puzzle::{ExampleAnswer, Puzzle};
+pub use scaffold::scaffold_day;
+pub use schedule::{next_event_unlock, next_unlock, unlock_time, wait_for_unlock};
+pub use stats::{day_statuses, parse_calendar, parse_personal_stats, DayStatus, LevelStats};
+pub use verdict::Verdict;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Level {
+    One,
+    Two,
+}
+
+impl FromStr for Level {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "1" => Ok(Level::One),
+            "2" => Ok(Level::Two),
+            _ => Err(anyhow!("Expected one of 1, 2")),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        {
+            match self {
+                Level::One => "1",
+                Level::Two => "2",
+            }
+        }
+        .fmt(f)
+    }
+}
+
+impl Level {
+    pub const ALL: [Level; 2] = [Level::One, Level::Two];
+}
+
+#[derive(Debug)]
+pub struct Submit;
+
+#[derive(Debug, Clone, Copy)]
+pub enum Days {
+    All,
+    One(u32),
+}
+
+/// Where a day's puzzle input is read from at runtime.
+#[derive(Debug, Clone, Default)]
+pub enum InputSource {
+    /// The conventional `input/dayN.txt`
+    #[default]
+    Default,
+    Path(PathBuf),
+    Stdin,
+    /// A file in `src/days/test_input`, either by full name (`day8_long`) or by the part
+    /// after the day prefix (`long`)
+    Example(String),
+}
+
+pub const INPUT_DIR: &str = "input";
+/// The day modules and the registry in `mod.rs`
+pub const DAYS_DIR: &str = "src/days";
+pub const TEST_INPUT_DIR: &str = "src/days/test_input";
+
+/// Where the input of `day` of `year` lives in `dir`. Inputs of [`YEAR`] keep the bare
+/// `dayN.txt`, other years get a subdirectory so they never overwrite them.
+pub fn input_path(dir: &Path, year: i32, day: u32) -> PathBuf {
+    if year == YEAR {
+        dir.join(format!("day{day}.txt"))
+    } else {
+        dir.join(year.to_string()).join(format!("day{day}.txt"))
+    }
+}
+
+impl InputSource {
+    pub fn load(&self, day: u32) -> anyhow::Result<String> {
+        let path = match self {
+            InputSource::Default => input_path(Path::new(INPUT_DIR), YEAR, day),
+            InputSource::Path(path) => path.clone(),
+            InputSource::Stdin => {
+                let mut input = String::new();
+                std::io::stdin()
+                    .read_to_string(&mut input)
+                    .context("reading input from stdin")?;
+                return Ok(input);
+            }
+            InputSource::Example(name) => {
+                let path = Path::new(TEST_INPUT_DIR).join(format!("{name}.txt"));
+                if path.exists() {
+                    path
+                } else {
+                    Path::new(TEST_INPUT_DIR).join(format!("day{day}_{name}.txt"))
+                }
+            }
+        };
+        std::fs::read_to_string(&path).with_context(|| format!("reading {}", path.display()))
+    }
+}
+
+/// The year this repository solves, used unless `AOC_YEAR` says otherwise.
+pub const YEAR: i32 = 2023;
+
+#[derive(Debug)]
+pub enum Command {
+    Run {
+        days: Days,
+        level: Option<Level>,
+        input: InputSource,
+        submit: Option<Submit>,
+        /// Wait out a submission cooldown and try again
+        wait: bool,
+        /// Write machine-readable results instead of the usual output
+        export: Option<Export>,
+    },
+    Fetch {
+        /// Overrides the configured year
+        year: Option<i32>,
+        day: u32,
+    },
+    Leaderboard {
+        /// Overrides the configured year
+        year: Option<i32>,
+        id: u64,
+    },
+    Stats {
+        /// Overrides the configured year
+        year: Option<i32>,
+    },
+    /// Checks the session and shows whose it is
+    Whoami,
+    WaitAndFetch {
+        /// Overrides the configured year
+        year: Option<i32>,
+        /// The next day to unlock if not given
+        day: Option<u32>,
+    },
+    New {
+        day: u32,
+    },
+    /// Rerun the examples and then the real input of a day whenever its files change
+    Watch {
+        day: u32,
+        /// Check once with the current build instead of watching
+        once: bool,
+    },
+    /// Time every phase of the selected days over many iterations
+    Bench {
+        days: Days,
+        iterations: usize,
+        output: PathBuf,
+        baseline: Option<PathBuf>,
+        /// Slowdown of a median, in percent, above which a phase counts as regressed
+        threshold: f64,
+    },
+    /// Compare every registered day against the recorded answers for the real input
+    Check {
+        /// First import the answers the site accepted, as recorded in `input/history.tsv`, for
+        /// levels that have no recorded answer yet
+        record: bool,
+    },
+    /// Show the example blocks of a puzzle, saving the chosen ones as test inputs
+    Examples {
+        day: u32,
+        /// Index of a block and the name of the test input to save it as
+        save: Vec<(usize, String)>,
+    },
+}
+
+fn path_arg(s: &std::ffi::OsStr) -> Result<PathBuf, std::convert::Infallible> {
+    Ok(PathBuf::from(s))
+}
+
+pub fn parse_args() -> anyhow::Result<Command> {
+    let mut pargs = pico_args::Arguments::from_env();
+    let command = match pargs.subcommand()?.as_deref() {
+        Some("run") => {
+            let days = if pargs.contains("--all") {
+                Days::All
+            } else {
+                Days::One(
+                    pargs
+                        .value_from_str("--day")
+                        .context("must pass --day or --all")?,
+                )
+            };
+            let level = pargs.opt_value_from_str("--level")?;
+            let input_path = pargs.opt_value_from_os_str("--input", path_arg)?;
+            let example: Option<String> = pargs.opt_value_from_str("--example")?;
+            let input = match (input_path, example) {
+                (None, None) => InputSource::Default,
+                (Some(path), None) if path.as_os_str() == "-" => InputSource::Stdin,
+                (Some(path), None) => InputSource::Path(path),
+                (None, Some(name)) => InputSource::Example(name),
+                (Some(_), Some(_)) => bail!("--input and --example are mutually exclusive"),
+            };
+            if matches!(days, Days::All) && !matches!(input, InputSource::Default) {
+                bail!("--input and --example need a single --day");
+            }
+            let submit = pargs.contains(["-s", "--submit"]).then_some(Submit);
+            if submit.is_some() && (matches!(days, Days::All) || level.is_none()) {
+                bail!("--submit needs a single --day and --level");
+            }
+            if submit.is_some() && !matches!(input, InputSource::Default) {
+                bail!("--submit only works with the puzzle input");
+            }
+            let wait = pargs.contains("--wait");
+            if wait && submit.is_none() {
+                bail!("--wait only makes sense with --submit");
+            }
+            let format = pargs.opt_value_from_str("--format")?;
+            let output = pargs.opt_value_from_os_str("--output", path_arg)?;
+            let export = match (format, output) {
+                (Some(format), output) => Some(Export { format, output }),
+                (None, Some(_)) => bail!("--output needs --format json or --format csv"),
+                (None, None) => None,
+            };
+            if export.is_some() && (submit.is_some() || !matches!(input, InputSource::Default)) {
+                bail!("--format only works with the puzzle input and without --submit");
+            }
+            Command::Run {
+                days,
+                level,
+                input,
+                submit,
+                wait,
+                export,
+            }
+        }
+        Some("fetch") => Command::Fetch {
+            year: pargs.opt_value_from_str("--year")?,
+            day: pargs.value_from_str("--day").context("must pass --day")?,
+        },
+        Some("leaderboard") => Command::Leaderboard {
+            year: pargs.opt_value_from_str("--year")?,
+            id: pargs
+                .value_from_str("--id")
+                .context("must pass the leaderboard --id")?,
+        },
+        Some("stats") => Command::Stats {
+            year: pargs.opt_value_from_str("--year")?,
+        },
+        Some("whoami") => Command::Whoami,
+        Some("wait-and-fetch") => Command::WaitAndFetch {
+            year: pargs.opt_value_from_str("--year")?,
+            day: pargs.opt_value_from_str("--day")?,
+        },
+        Some("new") => Command::New {
+            day: pargs
+                .free_from_str()
+                .context("must pass the day to create")?,
+        },
+        Some("watch") => Command::Watch {
+            day: pargs.value_from_str("--day").context("must pass --day")?,
+            once: pargs.contains("--once"),
+        },
+        Some("bench") => Command::Bench {
+            days: match pargs.opt_value_from_str("--day")? {
+                Some(day) => Days::One(day),
+                None => Days::All,
+            },
+            iterations: pargs.opt_value_from_str("--iterations")?.unwrap_or(20),
+            output: pargs
+                .opt_value_from_os_str("--output", path_arg)?
+                .unwrap_or_else(|| PathBuf::from("target/aoc-bench.json")),
+            baseline: pargs.opt_value_from_os_str("--baseline", path_arg)?,
+            threshold: pargs.opt_value_from_str("--threshold")?.unwrap_or(10.0),
+        },
+        Some("check") => Command::Check {
+            record: pargs.contains("--record"),
+        },
+        Some("examples") => Command::Examples {
+            day: pargs.value_from_str("--day").context("must pass --day")?,
+            save: pargs.values_from_fn("--save", |s| {
+                let (index, name) = s.split_once(':').context("expected INDEX:NAME")?;
+                anyhow::Ok((index.parse().context("block index")?, name.to_string()))
+            })?,
+        },
+        Some(other) => {
+            bail!("Unknown subcommand {other}, expected one of run, bench, check, fetch, wait-and-fetch, whoami, leaderboard, stats, new, examples, watch")
+        }
+        None => {
+            bail!("Missing subcommand, expected one of run, bench, check, fetch, wait-and-fetch, whoami, leaderboard, stats, new, examples, watch")
+        }
+    };
+    let rest = pargs.finish();
+    if !rest.is_empty() {
+        bail!("Unexpected arguments {rest:?}");
+    }
+    Ok(command)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn example_input_by_full_or_short_name() {
+        let full = InputSource::Example("day8_long".to_string())
+            .load(8)
+            .unwrap();
+        let short = InputSource::Example("long".to_string()).load(8).unwrap();
+        assert_eq!(full, short);
+        assert!(InputSource::Example("missing".to_string()).load(8).is_err());
+    }
+}
@@ -0,0 +1,101 @@
+use super::html::{elements, strip_tags, unescape};
+use crate::util::answer::Answer;
+
+/// The parts of a puzzle page that are useful for testing.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Puzzle {
+    /// Contents of every `<pre><code>` block, in page order
+    pub examples: Vec<String>,
+    /// The example answer of each visible level, if one could be found
+    pub answers: Vec<Option<ExampleAnswer>>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ExampleAnswer {
+    pub answer: Answer,
+    /// Index into [`Puzzle::examples`] of the block this is most likely the answer for: the
+    /// last one shown up to this level, since later levels often reuse earlier examples
+    pub block: Option<usize>,
+}
+
+impl Puzzle {
+    /// Reads the puzzle description articles. The second level only shows up once the first
+    /// one is solved.
+    pub fn parse(html: &str) -> Self {
+        let mut examples = Vec::new();
+        let mut answers = Vec::new();
+        for article in elements(html, "article") {
+            examples.extend(
+                elements(article, "pre")
+                    .into_iter()
+                    .flat_map(|pre| elements(pre, "code"))
+                    .map(|code| unescape(&strip_tags(code))),
+            );
+            answers.push(example_answer(article).map(|answer| ExampleAnswer {
+                answer,
+                block: examples.len().checked_sub(1),
+            }));
+        }
+        Self { examples, answers }
+    }
+}
+
+/// The site highlights the example's result as `<code><em>…</em></code>`; the last one in a
+/// level's description is the answer to the example.
+fn example_answer(article: &str) -> Option<Answer> {
+    elements(article, "code")
+        .into_iter()
+        .rev()
+        .find_map(|code| {
+            let code = code.trim();
+            let inner = code.strip_prefix("<em>")?.strip_suffix("</em>")?;
+            unescape(&strip_tags(inner)).parse().ok()
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::prelude::Itertools;
+
+    #[test]
+    fn both_levels() {
+        let puzzle = Puzzle::parse(include_str!("./test_input/puzzle_day1.html"));
+        assert_eq!(
+            puzzle.examples.iter().map(|s| s.trim_end()).collect_vec(),
+            vec![
+                include_str!("../../days/test_input/day1.txt"),
+                include_str!("../../days/test_input/day1_large.txt"),
+            ]
+        );
+        assert_eq!(
+            puzzle.answers,
+            vec![
+                Some(ExampleAnswer {
+                    answer: 142_u32.into(),
+                    block: Some(0)
+                }),
+                Some(ExampleAnswer {
+                    answer: 281_u32.into(),
+                    block: Some(1)
+                })
+            ]
+        );
+    }
+
+    #[test]
+    fn first_level_only() {
+        let puzzle = Puzzle::parse(include_str!("./test_input/puzzle_day3_part1.html"));
+        assert_eq!(
+            puzzle.examples.iter().map(|s| s.trim_end()).collect_vec(),
+            vec![include_str!("../../days/test_input/day3.txt")]
+        );
+        assert_eq!(
+            puzzle.answers,
+            vec![Some(ExampleAnswer {
+                answer: 4361_u32.into(),
+                block: Some(0)
+            })]
+        );
+    }
+}
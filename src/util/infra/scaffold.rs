@@ -0,0 +1,118 @@
+use std::path::Path;
+
+use anyhow::{anyhow, bail, Context};
+
+use super::{DAYS_DIR, TEST_INPUT_DIR};
+
+const TEMPLATE: &str = include_str!("../../../templates/library");
+
+/// Creates `src/days/dayN.rs` from the template, registers it in `src/days/mod.rs` and adds an
+/// empty example input. Nothing is written if any of these already exist.
+pub fn scaffold_day(root: &Path, day: u32) -> anyhow::Result<()> {
+    if !(1..=25).contains(&day) {
+        bail!("There is no puzzle for day {day}");
+    }
+    let days_dir = root.join(DAYS_DIR);
+    let module_path = days_dir.join(format!("day{day}.rs"));
+    let example_path = root.join(TEST_INPUT_DIR).join(format!("day{day}.txt"));
+    let registry_path = days_dir.join("mod.rs");
+    for path in [&module_path, &example_path] {
+        if path.exists() {
+            bail!("{} already exists", path.display());
+        }
+    }
+    let registry = std::fs::read_to_string(&registry_path)
+        .with_context(|| format!("reading {}", registry_path.display()))?;
+    let registry = register(&registry, day)?;
+
+    std::fs::write(&module_path, TEMPLATE)
+        .with_context(|| format!("writing {}", module_path.display()))?;
+    std::fs::write(&example_path, "")
+        .with_context(|| format!("writing {}", example_path.display()))?;
+    std::fs::write(&registry_path, registry)
+        .with_context(|| format!("writing {}", registry_path.display()))?;
+    Ok(())
+}
+
+/// Adds `day => dayN,` to the `register_days!` invocation, keeping the days sorted.
+fn register(registry: &str, day: u32) -> anyhow::Result<String> {
+    let start = registry
+        .find("register_days! {")
+        .ok_or_else(|| anyhow!("no register_days! block"))?;
+    let end = registry[start..]
+        .find("\n}")
+        .ok_or_else(|| anyhow!("unterminated register_days! block"))?
+        + start;
+    let mut lines = registry[start..end].lines().skip(1).collect::<Vec<_>>();
+    let registered = lines
+        .iter()
+        .map(|line| {
+            let (number, _) = line
+                .trim()
+                .split_once(" => ")
+                .ok_or_else(|| anyhow!("unexpected registry line {line}"))?;
+            number.parse::<u32>().context("day number")
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    if registered.contains(&day) {
+        bail!("day {day} is already registered");
+    }
+    let new_line = format!("    {day} => day{day},");
+    let position = registered.partition_point(|&d| d < day);
+    lines.insert(position, &new_line);
+
+    let header = &registry[start..][..registry[start..].find('\n').unwrap_or(0)];
+    Ok(format!(
+        "{}{header}\n{}{}",
+        &registry[..start],
+        lines.join("\n"),
+        &registry[end..]
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::scratch::TempDir;
+
+    const REGISTRY: &str = "use x;\n\nregister_days! {\n    1 => day1,\n    3 => day3,\n}\n";
+
+    #[test]
+    fn registers_in_order() {
+        assert_eq!(
+            register(REGISTRY, 2).unwrap(),
+            "use x;\n\nregister_days! {\n    1 => day1,\n    2 => day2,\n    3 => day3,\n}\n"
+        );
+        assert_eq!(
+            register(REGISTRY, 12).unwrap(),
+            "use x;\n\nregister_days! {\n    1 => day1,\n    3 => day3,\n    12 => day12,\n}\n"
+        );
+        assert!(register(REGISTRY, 3).is_err());
+    }
+
+    #[test]
+    fn scaffold_refuses_to_overwrite() {
+        let root = TempDir::new("scaffold");
+        let days_dir = root.join(DAYS_DIR);
+        std::fs::create_dir_all(days_dir.join("test_input")).unwrap();
+        std::fs::write(days_dir.join("mod.rs"), REGISTRY).unwrap();
+
+        scaffold_day(&root, 2).unwrap();
+        let module = std::fs::read_to_string(days_dir.join("day2.rs")).unwrap();
+        assert!(module.contains("impl Solution for Day"));
+        assert!(days_dir.join("test_input/day2.txt").exists());
+        assert!(std::fs::read_to_string(days_dir.join("mod.rs"))
+            .unwrap()
+            .contains("2 => day2,"));
+
+        std::fs::write(days_dir.join("day2.rs"), "work in progress").unwrap();
+        assert!(scaffold_day(&root, 2).is_err());
+        assert_eq!(
+            std::fs::read_to_string(days_dir.join("day2.rs")).unwrap(),
+            "work in progress"
+        );
+        // Registered but the module file is missing
+        assert!(scaffold_day(&root, 1).is_err());
+        assert!(!days_dir.join("day1.rs").exists());
+    }
+}
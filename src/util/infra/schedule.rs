@@ -0,0 +1,123 @@
+use std::time::{Duration, SystemTime};
+
+use super::clock::{countdown, Clock};
+
+/// Puzzles unlock at midnight in US Eastern Standard Time, i.e. 05:00 UTC.
+const UNLOCK_HOUR_UTC: u64 = 5;
+/// Waited on top of the unlock time, so a slightly fast local clock does not fetch too early.
+const UNLOCK_MARGIN: Duration = Duration::from_secs(2);
+
+/// Days since the Unix epoch of the given proleptic Gregorian date.
+fn days_from_civil(year: i32, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year } as i64;
+    let era = if year >= 0 { year } else { year - 399 } / 400;
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month as i64 + 9) % 12) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The instant at which the puzzle for `day` of `year` becomes available.
+pub fn unlock_time(year: i32, day: u32) -> SystemTime {
+    let days = days_from_civil(year, 12, day) as u64;
+    SystemTime::UNIX_EPOCH + Duration::from_secs(days * 86400 + UNLOCK_HOUR_UTC * 3600)
+}
+
+pub fn is_unlocked(year: i32, day: u32, now: SystemTime) -> bool {
+    now >= unlock_time(year, day)
+}
+
+/// The first puzzle of `year` that is still locked at `now`, if any. Puzzles only unlock on
+/// December 1st to 25th, so this is day 1 all year until December.
+pub fn next_unlock(year: i32, now: SystemTime) -> Option<u32> {
+    (1..=25).find(|&day| !is_unlocked(year, day, now))
+}
+
+/// The next puzzle to unlock in any year, as `(year, day)`: day 1 of the current year until
+/// December, then the days of this event, then day 1 of the next year once day 25 is out.
+pub fn next_event_unlock(clock: &impl Clock) -> (i32, u32) {
+    let now = clock.now();
+    let secs = now
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    // Within a year of the actual one, using the average Gregorian year of 365.2425 days
+    let approx = 1970 + (secs / 31_556_952) as i32;
+    (approx - 1..=approx + 1)
+        .find_map(|year| next_unlock(year, now).map(|day| (year, day)))
+        .unwrap_or((approx + 1, 1))
+}
+
+/// Counts down until the puzzle for `day` is unlocked, returning immediately if it already is.
+pub fn wait_for_unlock(clock: &impl Clock, year: i32, day: u32) {
+    if let Ok(wait) = unlock_time(year, day).duration_since(clock.now()) {
+        if !wait.is_zero() {
+            countdown(clock, wait + UNLOCK_MARGIN, &format!("day {day} unlocks"));
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::infra::clock::test::FakeClock;
+
+    #[test]
+    fn unlock_is_midnight_eastern() {
+        let day1 = SystemTime::UNIX_EPOCH + Duration::from_secs(1701406800);
+        assert_eq!(unlock_time(2023, 1), day1);
+        assert_eq!(
+            unlock_time(2023, 25),
+            day1 + Duration::from_secs(24 * 86400)
+        );
+        assert!(!is_unlocked(2023, 1, day1 - Duration::from_secs(1)));
+        assert!(is_unlocked(2023, 1, day1));
+    }
+
+    #[test]
+    fn next_unlock_stays_in_december() {
+        let at = |secs: u64| SystemTime::UNIX_EPOCH + Duration::from_secs(secs);
+        let day1 = 1701406800;
+        // Midsummer and the last second before midnight in UTC-5 on November 30th
+        assert_eq!(next_unlock(2023, at(1688000000)), Some(1));
+        assert_eq!(next_unlock(2023, at(day1 - 1)), Some(1));
+        assert_eq!(next_unlock(2023, at(day1)), Some(2));
+        // 23:00 UTC on December 3rd is still December 3rd in UTC-5
+        assert_eq!(next_unlock(2023, at(day1 + 2 * 86400 + 18 * 3600)), Some(4));
+        assert_eq!(next_unlock(2023, at(day1 + 24 * 86400 - 1)), Some(25));
+        assert_eq!(next_unlock(2023, at(day1 + 24 * 86400)), None);
+        assert_eq!(next_unlock(2024, at(day1 + 24 * 86400)), Some(1));
+    }
+
+    #[test]
+    fn next_event_follows_the_clock() {
+        let at = |time: SystemTime| next_event_unlock(&FakeClock::at(time));
+        assert_eq!(at(unlock_time(2026, 1) - Duration::from_secs(1)), (2026, 1));
+        assert_eq!(
+            at(unlock_time(2026, 1) - Duration::from_secs(200 * 86400)),
+            (2026, 1)
+        );
+        assert_eq!(at(unlock_time(2026, 7)), (2026, 8));
+        assert_eq!(
+            at(unlock_time(2026, 25) - Duration::from_secs(1)),
+            (2026, 25)
+        );
+        assert_eq!(at(unlock_time(2026, 25)), (2027, 1));
+        // 02:00 UTC on January 1st, still New Year's Eve in UTC-5
+        assert_eq!(
+            at(unlock_time(2026, 31) + Duration::from_secs(21 * 3600)),
+            (2027, 1)
+        );
+    }
+
+    #[test]
+    fn waits_until_unlock() {
+        let clock = FakeClock::at(unlock_time(2023, 5) - Duration::from_secs(90));
+        wait_for_unlock(&clock, 2023, 5);
+        assert_eq!(clock.slept.get(), Duration::from_secs(90) + UNLOCK_MARGIN);
+
+        let clock = FakeClock::at(unlock_time(2023, 5));
+        wait_for_unlock(&clock, 2023, 5);
+        assert_eq!(clock.slept.get(), Duration::ZERO);
+    }
+}
@@ -0,0 +1,160 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+use anyhow::{bail, Context};
+use regex::Regex;
+
+use super::{
+    html::{elements, strip_tags, unescape},
+    Level,
+};
+
+/// Stars per unlocked day, from the event calendar at `/{year}`.
+pub fn parse_calendar(html: &str) -> BTreeMap<u32, u8> {
+    let regex = Regex::new(r#"aria-label="Day (\d+)(?:, (one star|two stars))?""#).unwrap();
+    regex
+        .captures_iter(html)
+        .map(|captures| {
+            let stars = match captures.get(2).map(|m| m.as_str()) {
+                Some("two stars") => 2,
+                Some(_) => 1,
+                None => 0,
+            };
+            (captures[1].parse().unwrap(), stars)
+        })
+        .collect()
+}
+
+/// When and how well a level was solved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LevelStats {
+    /// As shown on the site, e.g. `00:05:12` or `>24h`
+    pub time: String,
+    pub rank: u32,
+}
+
+/// Rank and time of each solved level, from the personal stats at `/{year}/leaderboard/self`.
+pub fn parse_personal_stats(html: &str) -> anyhow::Result<BTreeMap<u32, [Option<LevelStats>; 2]>> {
+    let Some(table) = elements(html, "pre").into_iter().next() else {
+        bail!("no stats table on the page, the session is probably invalid");
+    };
+    unescape(&strip_tags(table))
+        .lines()
+        .filter(|line| line.trim_start().starts_with(|c: char| c.is_ascii_digit()))
+        .map(|line| {
+            let fields = line.split_whitespace().collect::<Vec<_>>();
+            let [day, time1, rank1, _, time2, rank2, _] = fields[..] else {
+                bail!("unexpected stats line {line:?}");
+            };
+            let level = |time: &str, rank: &str| -> anyhow::Result<_> {
+                Ok(if time == "-" {
+                    None
+                } else {
+                    Some(LevelStats {
+                        time: time.to_string(),
+                        rank: rank.parse().context("rank")?,
+                    })
+                })
+            };
+            Ok((
+                day.parse().context("day")?,
+                [level(time1, rank1)?, level(time2, rank2)?],
+            ))
+        })
+        .collect()
+}
+
+/// What the site knows about one day, next to whether this repository solves it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayStatus {
+    pub day: u32,
+    pub stars: u8,
+    pub levels: [Option<LevelStats>; 2],
+    pub registered: bool,
+}
+
+impl DayStatus {
+    /// The levels whose star was earned.
+    pub fn solved_levels(&self) -> impl Iterator<Item = Level> {
+        Level::ALL.into_iter().take(self.stars as usize)
+    }
+
+    /// What is missing for this day to be complete, if anything.
+    pub fn missing(&self) -> Option<&'static str> {
+        match (self.stars, self.registered) {
+            (2, true) => None,
+            (0, false) => Some("solver and stars"),
+            (_, false) => Some("solver"),
+            (_, true) => Some("stars"),
+        }
+    }
+}
+
+/// Combines calendar, personal stats and registered days into one row per day that is unlocked
+/// or registered.
+pub fn day_statuses(
+    calendar: &BTreeMap<u32, u8>,
+    stats: &BTreeMap<u32, [Option<LevelStats>; 2]>,
+    registered: &[u32],
+) -> Vec<DayStatus> {
+    calendar
+        .keys()
+        .chain(registered)
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .map(|day| DayStatus {
+            day,
+            stars: calendar.get(&day).copied().unwrap_or_default(),
+            levels: stats.get(&day).cloned().unwrap_or_default(),
+            registered: registered.contains(&day),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn stars_ranks_and_missing_days() {
+        let calendar = parse_calendar(include_str!("test_input/calendar_2023.html"));
+        assert_eq!(calendar, BTreeMap::from([(1, 2), (2, 1), (3, 0), (12, 2)]));
+
+        let stats = parse_personal_stats(include_str!("test_input/stats_2023.html")).unwrap();
+        assert_eq!(stats.len(), 3);
+        assert_eq!(
+            stats[&1],
+            [
+                Some(LevelStats {
+                    time: "00:05:12".to_string(),
+                    rank: 1493
+                }),
+                Some(LevelStats {
+                    time: ">24h".to_string(),
+                    rank: 98765
+                }),
+            ]
+        );
+        assert_eq!(stats[&2][1], None);
+
+        let statuses = day_statuses(&calendar, &stats, &[1, 2, 3, 4]);
+        let missing = statuses
+            .iter()
+            .map(|status| (status.day, status.missing()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            missing,
+            [
+                (1, None),
+                (2, Some("stars")),
+                (3, Some("stars")),
+                (4, Some("stars")),
+                (12, Some("solver")),
+            ]
+        );
+        assert_eq!(
+            statuses[1].solved_levels().collect::<Vec<_>>(),
+            [Level::One]
+        );
+    }
+}
@@ -0,0 +1,146 @@
+use std::{fmt::Display, time::Duration};
+
+use anyhow::{anyhow, Context};
+use regex::Regex;
+
+use super::html::{elements, strip_tags, unescape};
+
+/// The site's reaction to a submitted answer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    Incorrect,
+    TooHigh,
+    TooLow,
+    /// The level was already solved or is not unlocked yet
+    WrongLevel,
+    /// Recorded for levels that show a star on the site; never returned for a submission
+    AlreadySolved,
+    RateLimited {
+        wait: Duration,
+    },
+}
+
+impl Verdict {
+    /// Reads the verdict from the `<article>` of the page returned for an answer POST.
+    pub fn parse(html: &str) -> anyhow::Result<Self> {
+        let text = article_text(html).context("response has no <article>")?;
+        if text.contains("That's the right answer") {
+            Ok(Verdict::Correct)
+        } else if text.contains("That's not the right answer") {
+            Ok(if text.contains("your answer is too high") {
+                Verdict::TooHigh
+            } else if text.contains("your answer is too low") {
+                Verdict::TooLow
+            } else {
+                Verdict::Incorrect
+            })
+        } else if text.contains("You don't seem to be solving the right level") {
+            Ok(Verdict::WrongLevel)
+        } else if text.contains("You gave an answer too recently") {
+            let wait = parse_wait(&text).context("rate limit without wait time")?;
+            Ok(Verdict::RateLimited { wait })
+        } else {
+            Err(anyhow!("unrecognised answer response: {text}"))
+        }
+    }
+
+    pub fn is_success(&self) -> bool {
+        matches!(self, Verdict::Correct | Verdict::AlreadySolved)
+    }
+
+    /// Stable identifier, as written to the history and exports.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Verdict::Correct => "correct",
+            Verdict::Incorrect => "incorrect",
+            Verdict::TooHigh => "too_high",
+            Verdict::TooLow => "too_low",
+            Verdict::WrongLevel => "wrong_level",
+            Verdict::AlreadySolved => "already_solved",
+            Verdict::RateLimited { .. } => "rate_limited",
+        }
+    }
+}
+
+impl Display for Verdict {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Verdict::Correct => write!(f, "correct"),
+            Verdict::Incorrect => write!(f, "incorrect"),
+            Verdict::TooHigh => write!(f, "incorrect, too high"),
+            Verdict::TooLow => write!(f, "incorrect, too low"),
+            Verdict::WrongLevel => write!(f, "not the right level, already solved or still locked"),
+            Verdict::AlreadySolved => write!(f, "level already solved"),
+            Verdict::RateLimited { wait } => {
+                write!(f, "answered too recently, wait {}s", wait.as_secs())
+            }
+        }
+    }
+}
+
+/// The text content of the first `<article>`, with tags removed.
+fn article_text(html: &str) -> Option<String> {
+    let article = elements(html, "article").into_iter().next()?;
+    Some(unescape(strip_tags(article).trim()))
+}
+
+/// Reads waits like `You have 43s left to wait` or `You have 5m 3s left to wait`.
+fn parse_wait(text: &str) -> Option<Duration> {
+    let regex = Regex::new(r"You have (?:(\d+)m )?(\d+)s left to wait").unwrap();
+    let captures = regex.captures(text)?;
+    let minutes: u64 = captures.get(1).map_or(Ok(0), |m| m.as_str().parse()).ok()?;
+    let seconds: u64 = captures[2].parse().ok()?;
+    Some(Duration::from_secs(60 * minutes + seconds))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_saved_responses() {
+        for (html, verdict) in [
+            (
+                include_str!("./test_input/answer_correct.html"),
+                Verdict::Correct,
+            ),
+            (
+                include_str!("./test_input/answer_incorrect.html"),
+                Verdict::Incorrect,
+            ),
+            (
+                include_str!("./test_input/answer_too_high.html"),
+                Verdict::TooHigh,
+            ),
+            (
+                include_str!("./test_input/answer_too_low.html"),
+                Verdict::TooLow,
+            ),
+            (
+                include_str!("./test_input/answer_wrong_level.html"),
+                Verdict::WrongLevel,
+            ),
+            (
+                include_str!("./test_input/answer_rate_limited.html"),
+                Verdict::RateLimited {
+                    wait: Duration::from_secs(43),
+                },
+            ),
+            (
+                include_str!("./test_input/answer_rate_limited_minutes.html"),
+                Verdict::RateLimited {
+                    wait: Duration::from_secs(303),
+                },
+            ),
+        ] {
+            assert_eq!(Verdict::parse(html).unwrap(), verdict);
+        }
+    }
+
+    #[test]
+    fn unknown_response_is_an_error() {
+        assert!(Verdict::parse("<html><body>Bad gateway</body></html>").is_err());
+        assert!(Verdict::parse("<article><p>Something new</p></article>").is_err());
+    }
+}
@@ -0,0 +1,36 @@
+use std::borrow::Cow;
+
+/// Brings an input file into the shape the parsers expect: `\n` line endings, no blank lines
+/// before or after the content, and exactly one final newline. Inputs already in that shape are
+/// borrowed as they are.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let content = input.trim_matches(|c| c == '\n' || c == '\r');
+    if content.len() + 1 == input.len() && input.ends_with('\n') && !content.contains('\r') {
+        return Cow::Borrowed(input);
+    }
+    let mut normalized = content.replace("\r\n", "\n");
+    normalized.push('\n');
+    Cow::Owned(normalized)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn normalizes_line_endings_and_blank_lines() {
+        let expected = "a\n\nb\n";
+        for input in [
+            "a\n\nb\n",
+            "a\r\n\r\nb\r\n",
+            "a\n\nb",
+            "a\n\nb\n\n\n",
+            "\n\na\n\nb\n",
+            "\u{feff}a\r\n\r\nb",
+        ] {
+            assert_eq!(normalize(input), expected, "{input:?}");
+        }
+        assert!(matches!(normalize(expected), Cow::Borrowed(_)));
+    }
+}
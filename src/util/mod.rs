@@ -1,2 +1,15 @@
+pub mod alloc;
+pub mod answer;
+pub mod bench;
+pub mod diagnostic;
+pub mod examples;
+pub mod export;
 pub mod infra;
+pub mod input;
 pub mod prelude;
+pub mod registry;
+pub mod runner;
+#[cfg(test)]
+pub mod scratch;
+pub mod solution;
+pub mod watch;
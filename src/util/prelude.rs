@@ -1,3 +1,5 @@
+pub use super::diagnostic::Diagnose;
+pub use super::solution::Solution;
 pub use itertools::Itertools;
 use nom::{character::complete::line_ending, IResult, Parser};
 use nom_supreme::{error::ErrorTree, final_parser::Location};
@@ -72,11 +74,11 @@ impl<T> Grid<T> {
         }
     }
 
-    pub fn iter_rows(&self) -> impl Iterator<Item = std::slice::Iter<T>> {
+    pub fn iter_rows(&self) -> impl Iterator<Item = std::slice::Iter<'_, T>> {
         self.inner.chunks(self.length).map(|s| s.iter())
     }
 
-    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = std::slice::IterMut<T>> {
+    pub fn iter_rows_mut(&mut self) -> impl Iterator<Item = std::slice::IterMut<'_, T>> {
         self.inner.chunks_mut(self.length).map(|s| s.iter_mut())
     }
 
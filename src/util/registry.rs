@@ -0,0 +1,88 @@
+use std::time::{Duration, Instant};
+
+use anyhow::Context;
+
+use super::{
+    alloc::{measure, AllocStats},
+    answer::Answer,
+    infra::Level,
+    input::normalize,
+    solution::Solution,
+};
+
+/// Entry point for a single day, as registered in [`crate::days::DAYS`].
+#[derive(Debug, Clone, Copy)]
+pub struct Solver {
+    pub day: u32,
+    pub run: fn(&str, &[Level]) -> anyhow::Result<Run>,
+}
+
+#[derive(Debug)]
+pub struct LevelRun {
+    pub level: Level,
+    pub answer: Answer,
+    pub time: Duration,
+    /// Only measured with the `alloc-stats` feature
+    pub alloc: Option<AllocStats>,
+}
+
+#[derive(Debug)]
+pub struct Run {
+    pub parse_time: Duration,
+    pub parse_alloc: Option<AllocStats>,
+    pub levels: Vec<LevelRun>,
+}
+
+/// Normalizes and parses the input once and solves the requested levels on it, timing each phase.
+pub fn run<S: Solution>(input: &str, levels: &[Level]) -> anyhow::Result<Run> {
+    let input = normalize(input);
+    let input = input.as_ref();
+    let measurement = measure();
+    let start = Instant::now();
+    let parsed = S::parse(input).context("parsing input")?;
+    let parse_time = start.elapsed();
+    let parse_alloc = measurement.finish();
+    let levels = levels
+        .iter()
+        .map(|&level| {
+            let measurement = measure();
+            let start = Instant::now();
+            let answer = match level {
+                Level::One => S::part1(&parsed).map(Into::into),
+                Level::Two => S::part2(&parsed).map(Into::into),
+            }
+            .with_context(|| format!("solving level {level}"))?;
+            let time = start.elapsed();
+            Ok(LevelRun {
+                level,
+                answer,
+                time,
+                alloc: measurement.finish(),
+            })
+        })
+        .collect::<anyhow::Result<_>>()?;
+    Ok(Run {
+        parse_time,
+        parse_alloc,
+        levels,
+    })
+}
+
+/// Declares the day modules and collects them into `DAYS`, so adding a day is one line.
+macro_rules! register_days {
+    ($($day:literal => $module:ident,)*) => {
+        $(pub mod $module;)*
+
+        pub const DAYS: &[$crate::util::registry::Solver] = &[$(
+            $crate::util::registry::Solver {
+                day: $day,
+                run: $crate::util::registry::run::<$module::Day>,
+            },
+        )*];
+
+        pub fn get(day: u32) -> Option<&'static $crate::util::registry::Solver> {
+            DAYS.iter().find(|solver| solver.day == day)
+        }
+    };
+}
+pub(crate) use register_days;
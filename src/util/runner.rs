@@ -0,0 +1,160 @@
+use std::{
+    panic::{catch_unwind, AssertUnwindSafe},
+    path::Path,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::Duration,
+};
+
+use super::{
+    answer::Answer,
+    examples::{panic_message, Manifest, Status},
+    infra::{input_path, InputSource, Level, YEAR},
+    registry::Solver,
+};
+
+/// One level of one day in a [`run_parallel`] table.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Row {
+    pub day: u32,
+    pub level: Level,
+    /// Set whenever the level produced an answer, even a wrong one
+    pub answer: Option<Answer>,
+    pub time: Option<Duration>,
+    pub status: Status,
+}
+
+/// Solves `levels` of every solver on its input in `input_dir`, spreading the days over
+/// `workers` threads. Answers are compared against the recorded ones, and a panicking or
+/// failing day is reported in its rows instead of stopping the others. Rows are sorted by day
+/// and level.
+pub fn run_parallel(
+    solvers: &[&Solver],
+    levels: &[Level],
+    input_dir: &Path,
+    answers: &Manifest,
+    workers: usize,
+) -> Vec<Row> {
+    let next = AtomicUsize::new(0);
+    let rows = Mutex::new(Vec::new());
+    // Panic messages go into the table, not in between its rows
+    let hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(|_| {}));
+    std::thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, solvers.len().max(1)) {
+            scope.spawn(|| {
+                while let Some(solver) = solvers.get(next.fetch_add(1, Ordering::Relaxed)) {
+                    let day_rows = run_day(solver, levels, input_dir, answers);
+                    rows.lock().unwrap().extend(day_rows);
+                }
+            });
+        }
+    });
+    std::panic::set_hook(hook);
+    let mut rows = rows.into_inner().unwrap();
+    rows.sort_by_key(|row| (row.day, row.level));
+    rows
+}
+
+fn run_day(solver: &Solver, levels: &[Level], input_dir: &Path, answers: &Manifest) -> Vec<Row> {
+    let failed = |status: Status| {
+        levels
+            .iter()
+            .map(|&level| Row {
+                day: solver.day,
+                level,
+                answer: None,
+                time: None,
+                status: status.clone(),
+            })
+            .collect()
+    };
+    let path = input_path(input_dir, YEAR, solver.day);
+    if !path.exists() {
+        return failed(Status::MissingInput);
+    }
+    let input = match InputSource::Path(path).load(solver.day) {
+        Ok(input) => input,
+        Err(e) => return failed(Status::Error(format!("{e:#}"))),
+    };
+    let run = match catch_unwind(AssertUnwindSafe(|| (solver.run)(&input, levels))) {
+        Ok(Ok(run)) => run,
+        Ok(Err(e)) => return failed(Status::Error(format!("{e:#}"))),
+        Err(payload) => return failed(Status::Panicked(panic_message(&*payload))),
+    };
+    let file = format!("day{}", solver.day);
+    run.levels
+        .into_iter()
+        .map(|level_run| {
+            let status = match answers.get(&file, level_run.level) {
+                Some(expected) if *expected == level_run.answer => Status::Pass,
+                Some(_) => Status::Mismatch(level_run.answer.clone()),
+                None => Status::Unrecorded(level_run.answer.clone()),
+            };
+            Row {
+                day: solver.day,
+                level: level_run.level,
+                answer: Some(level_run.answer),
+                time: Some(level_run.time),
+                status,
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{
+        days,
+        util::{examples::Expectation, infra::TEST_INPUT_DIR, scratch::TempDir},
+    };
+
+    #[test]
+    fn reports_every_status_in_order() {
+        let dir = TempDir::new("runner");
+        std::fs::copy(
+            Path::new(TEST_INPUT_DIR).join("day7.txt"),
+            dir.join("day7.txt"),
+        )
+        .unwrap();
+        std::fs::write(dir.join("day9.txt"), "1 2 x\n").unwrap();
+        let mut answers = Manifest::default();
+        answers.expectations.push(Expectation {
+            file: "day7".to_string(),
+            level: Level::One,
+            answer: 6440_u32.into(),
+        });
+        answers.expectations.push(Expectation {
+            file: "day7".to_string(),
+            level: Level::Two,
+            answer: 1_u32.into(),
+        });
+
+        let solvers = [9, 8, 7].map(|day| days::get(day).unwrap());
+        let rows = run_parallel(&solvers, &Level::ALL, &dir, &answers, 2);
+
+        let summary = rows
+            .iter()
+            .map(|row| (row.day, row.level, row.answer.clone(), row.status.clone()))
+            .collect::<Vec<_>>();
+        assert!(matches!(&summary[4].3, Status::Error(e) if e.contains("line 1, column 5")));
+        assert_eq!(
+            summary[..4],
+            [
+                (7, Level::One, Some(6440_u32.into()), Status::Pass),
+                (
+                    7,
+                    Level::Two,
+                    Some(5905_u32.into()),
+                    Status::Mismatch(5905_u32.into())
+                ),
+                (8, Level::One, None, Status::MissingInput),
+                (8, Level::Two, None, Status::MissingInput),
+            ]
+        );
+        assert_eq!((summary[5].0, summary[5].1), (9, Level::Two));
+    }
+}
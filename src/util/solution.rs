@@ -0,0 +1,36 @@
+use super::{answer::Answer, input::normalize};
+
+/// A day's puzzle, split into a parse step and two parts working on the parsed input.
+pub trait Solution {
+    /// Parsed input, which may borrow from the raw input.
+    type Parsed<'a>;
+    type Answer1: Into<Answer>;
+    type Answer2: Into<Answer>;
+
+    fn parse(input: &str) -> anyhow::Result<Self::Parsed<'_>>;
+    fn part1(parsed: &Self::Parsed<'_>) -> anyhow::Result<Self::Answer1>;
+    fn part2(parsed: &Self::Parsed<'_>) -> anyhow::Result<Self::Answer2>;
+
+    /// Solves level 1 of a raw input, normalized as in [`run`](super::registry::run).
+    fn level1(input: &str) -> anyhow::Result<Self::Answer1> {
+        Self::part1(&Self::parse(&normalize(input))?)
+    }
+
+    /// Solves level 2 of a raw input, normalized as in [`run`](super::registry::run).
+    fn level2(input: &str) -> anyhow::Result<Self::Answer2> {
+        Self::part2(&Self::parse(&normalize(input))?)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::days::day7::Day;
+
+    #[test]
+    fn levels_normalize_the_input() {
+        let input = include_str!("../days/test_input/day7.txt").replace('\n', "\r\n");
+        assert_eq!(Day::level1(&input).unwrap(), 6440);
+        assert_eq!(Day::level2(input.trim_end()).unwrap(), 5905);
+    }
+}
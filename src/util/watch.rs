@@ -0,0 +1,248 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Write,
+    path::{Path, PathBuf},
+    time::{Duration, SystemTime},
+};
+
+use super::{
+    answer::Answer,
+    examples::{check_one, Manifest, Status},
+    infra::{Clock, Level},
+    runner::run_parallel,
+};
+use crate::days;
+
+/// How often the watched files are looked at.
+pub const POLL_INTERVAL: Duration = Duration::from_millis(300);
+
+/// The solver of `day` and every example input of that day. Files that do not exist yet are
+/// included, so creating them counts as a change.
+pub fn watched_paths(days_dir: &Path, test_input_dir: &Path, day: u32) -> Vec<PathBuf> {
+    let mut paths = vec![
+        days_dir.join(format!("day{day}.rs")),
+        test_input_dir.join(format!("day{day}.txt")),
+        test_input_dir.join(super::examples::MANIFEST_FILE),
+    ];
+    let prefix = format!("day{day}_");
+    if let Ok(entries) = std::fs::read_dir(test_input_dir) {
+        for entry in entries.flatten() {
+            let name = entry.file_name();
+            let name = name.to_string_lossy();
+            if name.starts_with(&prefix) && name.ends_with(".txt") {
+                paths.push(entry.path());
+            }
+        }
+    }
+    paths.sort();
+    paths
+}
+
+/// Modification times of the watched files, `None` for missing ones.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot(BTreeMap<PathBuf, Option<SystemTime>>);
+
+impl Snapshot {
+    pub fn take(paths: &[PathBuf]) -> Self {
+        Self(
+            paths
+                .iter()
+                .map(|path| {
+                    let modified = std::fs::metadata(path).and_then(|m| m.modified()).ok();
+                    (path.clone(), modified)
+                })
+                .collect(),
+        )
+    }
+
+    /// Paths that appeared, disappeared or were modified since `earlier`.
+    pub fn changed_since(&self, earlier: &Snapshot) -> Vec<PathBuf> {
+        let modified = self
+            .0
+            .iter()
+            .filter(|(path, modified)| earlier.0.get(*path) != Some(modified))
+            .map(|(path, _)| path);
+        let removed = earlier.0.keys().filter(|path| !self.0.contains_key(*path));
+        modified.chain(removed).cloned().collect()
+    }
+}
+
+/// Polls until one of the watched files of `day` changes, then waits for the writes to settle.
+/// Returns the new snapshot and the changed paths.
+pub fn wait_for_change(
+    clock: &impl Clock,
+    days_dir: &Path,
+    test_input_dir: &Path,
+    day: u32,
+    last: &Snapshot,
+) -> (Snapshot, Vec<PathBuf>) {
+    loop {
+        clock.sleep(POLL_INTERVAL);
+        let current = Snapshot::take(&watched_paths(days_dir, test_input_dir, day));
+        if current == *last {
+            continue;
+        }
+        // Editors often write a file in several steps
+        clock.sleep(POLL_INTERVAL);
+        let settled = Snapshot::take(&watched_paths(days_dir, test_input_dir, day));
+        let changed = settled.changed_since(last);
+        return (settled, changed);
+    }
+}
+
+/// The outcome of one level on one input in a [`check_day`] pass.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Line {
+    /// Name of the input file, without extension
+    pub file: String,
+    pub level: Level,
+    pub expected: Option<Answer>,
+    pub status: Status,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DayCheck {
+    pub examples: Vec<Line>,
+    /// `None` while an example fails
+    pub real: Option<Vec<Line>>,
+}
+
+impl DayCheck {
+    pub fn is_success(&self) -> bool {
+        let passed = |lines: &[Line]| {
+            lines
+                .iter()
+                .all(|line| matches!(line.status, Status::Pass | Status::Unrecorded(_)))
+        };
+        passed(&self.examples) && self.real.as_deref().is_some_and(passed)
+    }
+
+    /// One line per level, with the expected and actual answers below every mismatch.
+    pub fn render(&self) -> String {
+        let mut out = String::new();
+        if self.examples.is_empty() {
+            out.push_str("no examples in the manifest\n");
+        }
+        for line in &self.examples {
+            render_line(&mut out, line);
+        }
+        match &self.real {
+            Some(lines) => lines.iter().for_each(|line| render_line(&mut out, line)),
+            None => out.push_str("real input skipped until the examples pass\n"),
+        }
+        out
+    }
+}
+
+fn render_line(out: &mut String, line: &Line) {
+    let label = format!("{:<20} level {}", line.file, line.level);
+    let _ = match (&line.status, &line.expected) {
+        (Status::Pass, _) => writeln!(out, "  ok {label}  {}", line.expected.as_ref().unwrap()),
+        (Status::Mismatch(actual), Some(expected)) => {
+            writeln!(out, "FAIL {label}\n     - {expected}\n     + {actual}")
+        }
+        (Status::Unrecorded(actual), _) => writeln!(out, "   ? {label}  {actual}"),
+        (status, _) => writeln!(out, "FAIL {label}  {status}"),
+    };
+}
+
+/// Runs the examples of `day` and, once they all pass, its real input in `input_dir`.
+pub fn check_day(
+    day: u32,
+    examples: &Manifest,
+    test_input_dir: &Path,
+    answers: &Manifest,
+    input_dir: &Path,
+) -> DayCheck {
+    let examples = examples
+        .expectations
+        .iter()
+        .filter(|expectation| expectation.day() == Some(day))
+        .map(|expectation| Line {
+            file: expectation.file.clone(),
+            level: expectation.level,
+            expected: Some(expectation.answer.clone()),
+            status: check_one(expectation, test_input_dir),
+        })
+        .collect::<Vec<_>>();
+    let file = format!("day{day} input");
+    let real = examples
+        .iter()
+        .all(|line| line.status == Status::Pass)
+        .then(|| match days::get(day) {
+            Some(solver) => run_parallel(&[solver], &Level::ALL, input_dir, answers, 1)
+                .into_iter()
+                .map(|row| Line {
+                    file: file.clone(),
+                    level: row.level,
+                    expected: answers.get(&format!("day{day}"), row.level).cloned(),
+                    status: row.status,
+                })
+                .collect(),
+            None => vec![Line {
+                file,
+                level: Level::One,
+                expected: None,
+                status: Status::Error(format!("day {day} is not registered")),
+            }],
+        });
+    DayCheck { examples, real }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::util::{
+        examples::Expectation,
+        infra::{FakeClock, TEST_INPUT_DIR},
+        scratch::TempDir,
+    };
+
+    #[test]
+    fn mismatches_show_expected_and_actual() {
+        let mut examples = Manifest::default();
+        examples.expectations = vec![
+            Expectation {
+                file: "day7".to_string(),
+                level: Level::One,
+                answer: 6440_u32.into(),
+            },
+            Expectation {
+                file: "day7".to_string(),
+                level: Level::Two,
+                answer: 1_u32.into(),
+            },
+        ];
+        let answers = Manifest::default();
+        let input_dir = Path::new(TEST_INPUT_DIR);
+        let check = check_day(7, &examples, input_dir, &answers, input_dir);
+        assert!(!check.is_success());
+        assert_eq!(
+            check.render(),
+            "  ok day7                 level 1  6440\n\
+             FAIL day7                 level 2\n     - 1\n     + 5905\n\
+             real input skipped until the examples pass\n"
+        );
+
+        examples.expectations.pop();
+        let check = check_day(7, &examples, input_dir, &answers, input_dir);
+        assert!(check.is_success());
+        assert!(check
+            .render()
+            .ends_with("   ? day7 input           level 2  5905\n"));
+    }
+
+    #[test]
+    fn notices_new_and_modified_files() {
+        let dir = TempDir::new("watch");
+        std::fs::write(dir.join("day3.rs"), "").unwrap();
+        std::fs::write(dir.join("day30_other.txt"), "").unwrap();
+        let before = Snapshot::take(&watched_paths(&dir, &dir, 3));
+
+        std::fs::write(dir.join("day3_large.txt"), "1\n").unwrap();
+        let clock = FakeClock::at(SystemTime::now());
+        let (after, changed) = wait_for_change(&clock, &dir, &dir, 3, &before);
+        assert_eq!(changed, vec![dir.join("day3_large.txt")]);
+        assert_eq!(after, Snapshot::take(&watched_paths(&dir, &dir, 3)));
+    }
+}